@@ -0,0 +1,71 @@
+//! Compares `set_*_json` with `set_*_raw_json` for a large IAM-style policy, using
+//! the test bindings in `tests/fake`. Run with `cargo bench --bench raw_json`.
+#[allow(dead_code, unused_imports)]
+#[path = "../tests/fake/mod.rs"]
+mod fake;
+
+use std::{
+    hint::black_box,
+    path::Path,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use serde_json::{
+    json,
+    Value,
+};
+use terrars::*;
+use fake::*;
+
+const ITERATIONS: u32 = 20;
+
+fn policy() -> Value {
+    return json!({
+        "Version": "2012-10-17",
+        "Statement": (0 .. 5000).map(|i| json!({
+            "Sid": format!("Statement{}", i),
+            "Effect": "Allow",
+            "Action": ["s3:GetObject", "s3:PutObject", "s3:ListBucket"],
+            "Resource": [format!("arn:aws:s3:::bucket-{}", i), format!("arn:aws:s3:::bucket-{}/*", i)],
+            "Condition": {
+                "StringEquals": {
+                    "aws:PrincipalTag/team": format!("team-{}", i)
+                }
+            }
+        })).collect::<Vec<_>>()
+    });
+}
+
+fn bench(name: &str, mut f: impl FnMut() -> usize) {
+    let mut total = Duration::ZERO;
+    let mut size = 0;
+    for _ in 0 .. ITERATIONS {
+        let start = Instant::now();
+        size = black_box(f());
+        total += start.elapsed();
+    }
+    println!("{}: {:?} per iteration, {} bytes serialized", name, total / ITERATIONS, size);
+}
+
+fn main() {
+    let policy = policy();
+    let policy_json = serde_json::to_string(&policy).unwrap();
+    bench("set_name_json", || {
+        let stack = &mut BuildStack {}.build();
+        BuildThing {
+            tf_id: "t".into(),
+            name: "".into(),
+        }.build(stack).set_name_json(&policy);
+        return stack.serialize(Path::new("state.tfstate")).unwrap().len();
+    });
+    bench("set_name_raw_json", || {
+        let stack = &mut BuildStack {}.build();
+        BuildThing {
+            tf_id: "t".into(),
+            name: "".into(),
+        }.build(stack).set_name_raw_json(&policy_json);
+        return stack.serialize(Path::new("state.tfstate")).unwrap().len();
+    });
+}
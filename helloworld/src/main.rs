@@ -19,7 +19,7 @@ async fn main() -> Result<(), std::io::Error> {
                             println!("Got message {}", String::from_utf8_lossy(msg));
                             match socket.send_to(msg, addr) {
                                 Ok(_) => { },
-                                Err(e) => println!("Send to {} error: {:?}", addr.to_string(), e),
+                                Err(e) => println!("Send to {} error: {:?}", addr, e),
                             }
                             error_number = 0;
                         },
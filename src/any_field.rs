@@ -0,0 +1,82 @@
+use serde::Serialize;
+use serde_json::Value;
+use crate::{
+    TfPrimitiveType,
+    PrimType,
+    prim_ref::PrimExpr,
+    any_ref::AnyRef,
+    Expr,
+};
+
+/// A field for attributes of Terraform's `dynamic` (any) type. This can be any json
+/// value or an expression of any type.
+///
+/// Strings in literal values are escaped like other literal strings, but may
+/// contain expressions converted to strings.
+#[derive(Clone, PartialEq)]
+pub enum AnyField {
+    Literal(Value),
+    Sentinel(String),
+}
+
+impl Default for AnyField {
+    fn default() -> Self {
+        AnyField::Literal(Value::Null)
+    }
+}
+
+struct SerializeAnyValue<'a>(&'a Value);
+
+impl<'a> Serialize for SerializeAnyValue<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer {
+        match self.0 {
+            Value::String(s) => s.serialize2(serializer),
+            Value::Array(a) => serializer.collect_seq(a.iter().map(SerializeAnyValue)),
+            Value::Object(o) => serializer.collect_map(o.iter().map(|(k, v)| (k, SerializeAnyValue(v)))),
+            v => v.serialize(serializer),
+        }
+    }
+}
+
+impl Serialize for AnyField {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer {
+        match self {
+            AnyField::Literal(v) => SerializeAnyValue(v).serialize(serializer),
+            AnyField::Sentinel(r) => r.serialize2(serializer),
+        }
+    }
+}
+
+impl From<Value> for AnyField {
+    fn from(v: Value) -> Self {
+        AnyField::Literal(v)
+    }
+}
+
+impl<T: PrimType> From<PrimExpr<T>> for AnyField {
+    fn from(v: PrimExpr<T>) -> Self {
+        AnyField::Sentinel(v.expr_sentinel())
+    }
+}
+
+impl<T: PrimType> From<&PrimExpr<T>> for AnyField {
+    fn from(v: &PrimExpr<T>) -> Self {
+        AnyField::Sentinel(v.expr_sentinel())
+    }
+}
+
+impl From<&AnyRef> for AnyField {
+    fn from(v: &AnyRef) -> Self {
+        AnyField::Sentinel(v.shared.add_sentinel(&v.base))
+    }
+}
+
+impl From<AnyRef> for AnyField {
+    fn from(v: AnyRef) -> Self {
+        (&v).into()
+    }
+}
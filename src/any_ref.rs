@@ -0,0 +1,70 @@
+use crate::{
+    StackShared,
+    PrimType,
+    prim_ref::PrimExpr,
+    list_ref::ListRef,
+    rec_ref::RecRef,
+    set_ref::SetRef,
+    Ref,
+};
+
+/// A reference to a value of Terraform's `dynamic` (any) type. Since the type is
+/// unknown, convert it to the type you expect with the `as_*` methods.
+pub struct AnyRef {
+    pub(crate) shared: StackShared,
+    pub(crate) base: String,
+}
+
+impl Ref for AnyRef {
+    fn new(shared: StackShared, base: String) -> Self {
+        AnyRef {
+            shared: shared,
+            base: base,
+        }
+    }
+}
+
+impl AnyRef {
+    /// Refer to an attribute of the value, if it's an object.
+    pub fn attr(&self, name: &str) -> AnyRef {
+        AnyRef::new(self.shared.clone(), format!("{}.{}", self.base, name))
+    }
+
+    /// Refer to an attribute of the value as a primitive, or `null` if the attribute
+    /// doesn't exist (`try(value.name, null)`). Use this for data whose shape varies,
+    /// where `attr` would fail the plan when the attribute is missing. Note that
+    /// `try` also turns other errors in evaluating the attribute into `null`.
+    pub fn try_attr<T: PrimType>(&self, name: &str) -> PrimExpr<T> {
+        PrimExpr::new(self.shared.clone(), format!("try({}.{}, null)", self.base, name))
+    }
+
+    /// Refer to an element of the value, if it's a list or tuple.
+    pub fn index(&self, index: usize) -> AnyRef {
+        AnyRef::new(self.shared.clone(), format!("{}[{}]", self.base, index))
+    }
+
+    /// Refer to an element of the value, if it's a map or object.
+    pub fn key(&self, key: impl ToString) -> AnyRef {
+        AnyRef::new(self.shared.clone(), format!("{}[\"{}\"]", self.base, key.to_string()))
+    }
+
+    pub fn as_expr<T: PrimType>(&self) -> PrimExpr<T> {
+        PrimExpr::new(self.shared.clone(), self.base.clone())
+    }
+
+    pub fn as_list<T: Ref>(&self) -> ListRef<T> {
+        ListRef::new(self.shared.clone(), self.base.clone())
+    }
+
+    pub fn as_set<T: Ref>(&self) -> SetRef<T> {
+        SetRef::new(self.shared.clone(), self.base.clone())
+    }
+
+    pub fn as_rec<T: Ref>(&self) -> RecRef<T> {
+        RecRef::new(self.shared.clone(), self.base.clone())
+    }
+
+    pub fn raw(&self) -> String {
+        self.base.clone()
+    }
+}
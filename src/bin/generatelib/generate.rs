@@ -1,12 +1,20 @@
-use std::collections::BTreeMap;
+use std::collections::{
+    BTreeMap,
+    HashSet,
+};
+use loga::{
+    ea,
+    ResultContext,
+};
 use proc_macro2::{
+    Ident,
     TokenStream,
 };
 use quote::{
     format_ident,
     quote,
 };
-use super::sourceschema::{
+use terrars::sourceschema::{
     ScalarTypeKey,
     ValueBehaviorHelper,
     Value,
@@ -26,12 +34,13 @@ pub fn generate_simple_type(t: &ScalarTypeKey) -> (TokenStream, Option<(TokenStr
         ScalarTypeKey::Integer => quote!(i64),
         ScalarTypeKey::String => quote!(String),
         ScalarTypeKey::Bool => quote!(bool),
+        ScalarTypeKey::Dynamic => return (quote!(AnyField), Some((quote!(AnyRef), quote!(AnyRef)))),
     };
     (quote!(PrimField < #raw >), Some((quote!(PrimExpr), quote!(PrimExpr < #raw >))))
 }
 
-pub fn add_path(v: &Vec<String>, e: &str) -> Vec<String> {
-    let mut out = v.clone();
+pub fn add_path(v: &[String], e: &str) -> Vec<String> {
+    let mut out = v.to_vec();
     for s in e.split("_") {
         out.push(s.to_string());
     }
@@ -39,7 +48,7 @@ pub fn add_path(v: &Vec<String>, e: &str) -> Vec<String> {
 }
 
 pub fn to_camel(v: &[String]) -> String {
-    v.iter().map(|s| format!("{}{}", (&s[..1].to_string()).to_uppercase(), &s[1..])).collect()
+    v.iter().map(|s| format!("{}{}", s[..1].to_string().to_uppercase(), &s[1..])).collect()
 }
 
 pub fn to_snake(v: &[String]) -> String {
@@ -106,6 +115,45 @@ pub fn sanitize(v: &str) -> (bool, String) {
     }
 }
 
+/// Rename resource/datasource module names that would collide with the modules
+/// always generated (`provider`, `mod`) or with Rust keywords by adding a `_`
+/// suffix.
+pub fn module_name(v: String) -> String {
+    match v.as_str() {
+        "provider" | "mod" => format!("{}_", v),
+        s => sanitize(s).1,
+    }
+}
+
+/// Per-attribute generation settings from the config. Attributes are identified by
+/// a dotted path starting with the resource/datasource name, ex:
+/// `iam_role.assume_role_policy` or `data_iam_policy_document.statement.actions`.
+#[derive(Default)]
+pub struct GenerateContext {
+    /// String attributes that contain serialized JSON.
+    pub json_attributes: HashSet<String>,
+    /// String attributes that contain a duration (ex: `30s`).
+    pub duration_attributes: HashSet<String>,
+    /// String attributes that contain a size (ex: `10Gi`).
+    pub size_attributes: HashSet<String>,
+    /// String attributes with a fixed set of allowed values, which get an enum type.
+    pub enum_attributes: BTreeMap<String, Vec<String>>,
+}
+
+pub fn add_attr_path(attr_path: &str, k: &str) -> String {
+    format!("{}.{}", attr_path, k)
+}
+
+/// The `self` pattern, field access, and attributes for setters. Setters on types
+/// without identity return the modified value, so ignoring it loses the setting.
+fn mut_self(self_has_identity: bool) -> (TokenStream, TokenStream, TokenStream) {
+    if self_has_identity {
+        (quote!(self), quote!(self.0.data.borrow_mut()), quote!())
+    } else {
+        (quote!(mut self), quote!(self), quote!(#[must_use]))
+    }
+}
+
 #[derive(Default)]
 pub struct TopLevelFields {
     pub extra_types: Vec<TokenStream>,
@@ -115,21 +163,31 @@ pub struct TopLevelFields {
     pub mut_methods: Vec<TokenStream>,
     pub builder_fields: Vec<TokenStream>,
     pub copy_builder_fields: Vec<TokenStream>,
-    pub dynamic_block_fields: Vec<TokenStream>,
+    /// Block field names and element types, for blocks that can be set dynamically
+    pub dynamic_block_fields: Vec<(Ident, TokenStream)>,
 }
 
 impl TopLevelFields {
     pub fn finish(&mut self, camel_name: &str) {
         if !self.dynamic_block_fields.is_empty() {
             let dynamic_ident = format_ident!("{}Dynamic", camel_name);
-            let dynamic_fields = self.dynamic_block_fields.split_off(0);
+            let (dynamic_names, dynamic_types): (Vec<Ident>, Vec<TokenStream>) =
+                self.dynamic_block_fields.split_off(0).into_iter().unzip();
+            let is_empty = format!("{}::is_empty", dynamic_ident);
             self.extra_types.push(quote!{
                 #[derive(Serialize, Default)] struct #dynamic_ident {
-                    #(#dynamic_fields,) *
+                    #(
+                        #[serde(skip_serializing_if = "Option::is_none")] #dynamic_names: Option < DynamicBlock < #dynamic_types >>,
+                    ) *
+                }
+                impl #dynamic_ident {
+                    fn is_empty(&self) -> bool {
+                        #(self.#dynamic_names.is_none()) && *
+                    }
                 }
             });
             self.fields.push(quote!{
-                dynamic: #dynamic_ident
+                #[serde(skip_serializing_if = #is_empty)] dynamic: #dynamic_ident
             });
             self.copy_builder_fields.push(quote!{
                 dynamic: Default::default()
@@ -138,6 +196,7 @@ impl TopLevelFields {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn generate_field(
     out: &mut TopLevelFields,
     k: &str,
@@ -152,10 +211,17 @@ pub fn generate_field(
     let field_name = format_ident!("{}", sanitized_name);
     let set_field_name = format_ident!("set_{}", k);
     let set_doc = format!("Set the field `{}`.\n{}", field_name, field_doc);
+    let set_opt_field_name = format_ident!("set_{}_opt", k);
+    let set_opt_doc = format!("Set the field `{}` if `v` is `Some`, otherwise leave it unchanged.", field_name);
     let ref_doc = format!("Get a reference to the value of field `{}` after provisioning.\n{}", field_name, field_doc);
     match behavior {
         ValueBehaviorHelper::UserRequired => {
-            out.builder_fields.push(quote!(#[doc = #field_doc] pub #field_name: #rust_field_type));
+            let builder_doc = if field_doc.is_empty() {
+                quote!()
+            } else {
+                quote!(#[doc = #field_doc])
+            };
+            out.builder_fields.push(quote!(#builder_doc pub #field_name: #rust_field_type));
             out.copy_builder_fields.push(quote!(#field_name: self.#field_name));
             if sanitized {
                 out.fields.push(quote!(#[serde(rename = #k)] #field_name: #rust_field_type));
@@ -184,24 +250,16 @@ pub fn generate_field(
                         ),
                     );
             }
-            let pat_mut_self;
-            let access_mut_self;
-            if self_has_identity {
-                pat_mut_self = quote!(self);
-                access_mut_self = quote!(self.0.data.borrow_mut());
-            } else {
-                pat_mut_self = quote!(mut self);
-                access_mut_self = quote!(self);
-            }
+            let (pat_mut_self, access_mut_self, must_use) = mut_self(self_has_identity);
             if let Some(block_type) = block {
-                out.dynamic_block_fields.push(quote!(#field_name: Option < DynamicBlock < #block_type >>));
+                out.dynamic_block_fields.push((field_name.clone(), block_type.clone()));
                 out
                     .mut_methods
                     .push(
                         quote!(
                             #[
                                 doc = #set_doc
-                            ] pub fn #set_field_name(
+                            ] #must_use pub fn #set_field_name(
                                 #pat_mut_self,
                                 v: impl Into < BlockAssignable < #block_type >>
                             ) -> Self {
@@ -224,12 +282,29 @@ pub fn generate_field(
                         quote!(
                             #[
                                 doc = #set_doc
-                            ] pub fn #set_field_name(#pat_mut_self, v: impl Into < #rust_field_type >) -> Self {
+                            ] #must_use pub fn #set_field_name(#pat_mut_self, v: impl Into < #rust_field_type >) -> Self {
                                 #access_mut_self.#field_name = Some(v.into());
                                 self
                             }
                         ),
                     );
+                out
+                    .mut_methods
+                    .push(
+                        quote!(
+                            #[
+                                doc = #set_opt_doc
+                            ] #must_use pub fn #set_opt_field_name(
+                                #pat_mut_self,
+                                v: Option < #rust_field_type >
+                            ) -> Self {
+                                if let Some(v) = v {
+                                    #access_mut_self.#field_name = Some(v);
+                                }
+                                self
+                            }
+                        ),
+                    );
             }
         },
         ValueBehaviorHelper::Computed => {
@@ -238,9 +313,8 @@ pub fn generate_field(
     }
     if let Some((t1, t2)) = rust_field_ref_type {
         if self_has_identity {
-            let ref_fmt = format!("{{}}.{}", k);
             out.ref_methods.push(quote!(#[doc = #ref_doc] pub fn #field_name(&self) -> #t2 {
-                #t1:: new(self.shared().clone(), format!(#ref_fmt, self.extract_ref()))
+                #t1:: new(self.shared().clone(), self.attr_ref(#k))
             }));
         }
         let ref_ref_fmt = format!("{{}}.{}", k);
@@ -250,75 +324,310 @@ pub fn generate_field(
     }
 }
 
+/// Adds setters for a string field holding JSON, that take any serializable value
+/// or a JSON string.
+pub fn generate_json_setter(out: &mut TopLevelFields, k: &str, behavior: &ValueBehaviorHelper, self_has_identity: bool) {
+    let (_, sanitized_name) = sanitize(k);
+    let field_name = format_ident!("{}", sanitized_name);
+    let set_field_name = format_ident!("set_{}_json", k);
+    let set_doc = format!("Set the field `{}` to the JSON serialization of `v`.", field_name);
+    let (pat_mut_self, access_mut_self, must_use) = mut_self(self_has_identity);
+    let value = match behavior {
+        ValueBehaviorHelper::UserRequired => quote!(serde_json::to_string(v).unwrap().into()),
+        ValueBehaviorHelper::UserOptional | ValueBehaviorHelper::UserOptionalComputed => quote!(
+            Some(serde_json::to_string(v).unwrap().into())
+        ),
+        ValueBehaviorHelper::Computed => return,
+    };
+    out.mut_methods.push(quote!(#[doc = #set_doc] #must_use pub fn #set_field_name(#pat_mut_self, v:& impl Serialize) -> Self {
+        #access_mut_self.#field_name = #value;
+        self
+    }));
+    let set_raw_field_name = format_ident!("set_{}_raw_json", k);
+    let set_raw_doc =
+        format!(
+            "Set the field `{}` to already serialized JSON, for instance a large document loaded from a file. `v` is stored as-is without being parsed or checked, so invalid JSON is only reported by Terraform or the provider.",
+            field_name
+        );
+    let raw_value = match behavior {
+        ValueBehaviorHelper::UserRequired => quote!(v.to_string().into()),
+        _ => quote!(Some(v.to_string().into())),
+    };
+    out.mut_methods.push(quote!(#[doc = #set_raw_doc] #must_use pub fn #set_raw_field_name(#pat_mut_self, v:& str) -> Self {
+        #access_mut_self.#field_name = #raw_value;
+        self
+    }));
+}
+
+/// Adds `set_*_duration` and `set_*_size` setters for string attributes listed in
+/// the config as durations or sizes.
+pub fn generate_formatted_setters(
+    ctx: &GenerateContext,
+    out: &mut TopLevelFields,
+    attr_path: &str,
+    k: &str,
+    behavior: &ValueBehaviorHelper,
+    self_has_identity: bool,
+) {
+    let field_name = format_ident!("{}", sanitize(k).1);
+    let (pat_mut_self, access_mut_self, must_use) = mut_self(self_has_identity);
+    let mut setters = vec![];
+    if ctx.duration_attributes.contains(attr_path) {
+        setters.push((
+            format_ident!("set_{}_duration", k),
+            format!("Set the field `{}` to a duration, formatted as seconds (ex: `90s`).", field_name),
+            quote!(std:: time:: Duration),
+            quote!(format_duration(v)),
+        ));
+    }
+    if ctx.size_attributes.contains(attr_path) {
+        setters.push((
+            format_ident!("set_{}_size", k),
+            format!(
+                "Set the field `{}` to a size in bytes, formatted with a binary suffix (ex: `10Gi`).",
+                field_name
+            ),
+            quote!(u64),
+            quote!(format_size(v)),
+        ));
+    }
+    for (set_field_name, set_doc, arg_type, format) in setters {
+        let value = match behavior {
+            ValueBehaviorHelper::UserRequired => quote!(#format.into()),
+            ValueBehaviorHelper::UserOptional | ValueBehaviorHelper::UserOptionalComputed => quote!(
+                Some(#format.into())
+            ),
+            ValueBehaviorHelper::Computed => return,
+        };
+        out.mut_methods.push(quote!(#[doc = #set_doc] #must_use pub fn #set_field_name(#pat_mut_self, v: #arg_type) -> Self {
+            #access_mut_self.#field_name = #value;
+            self
+        }));
+    }
+}
+
+/// Adds an enum of the allowed values for a string attribute listed in the config's
+/// `enums`, which converts into the attribute's field type.
+pub fn generate_enum_type(
+    out: &mut TopLevelFields,
+    attr_path: &str,
+    path: &[String],
+    values: &[String],
+) -> Result<(), loga::Error> {
+    let enum_ident = format_ident!("{}", to_camel(path));
+    let mut variants = vec![];
+    let mut seen = HashSet::new();
+    for value in values {
+        let parts =
+            value
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|p| !p.is_empty())
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+        if parts.is_empty() {
+            return Err(
+                loga::err_with(
+                    "Enum value has no letters or digits to name a variant",
+                    ea!(attribute = attr_path, value = value),
+                ),
+            );
+        }
+        let mut variant = to_camel(&parts);
+        if variant.starts_with(|c: char| c.is_ascii_digit()) {
+            variant = format!("V{}", variant);
+        }
+        if !seen.insert(variant.clone()) {
+            return Err(
+                loga::err_with(
+                    "Enum values produce the same variant name",
+                    ea!(attribute = attr_path, value = value, variant = variant),
+                ),
+            );
+        }
+        variants.push((format_ident!("{}", variant), value));
+    }
+    let variant_idents = variants.iter().map(|(i, _)| i).collect::<Vec<_>>();
+    let variant_values = variants.iter().map(|(_, v)| v).collect::<Vec<_>>();
+    let doc = format!("Allowed values for `{}`, from the generation config.", attr_path);
+    out.extra_types.push(quote!{
+        #[doc = #doc] #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)] pub enum #enum_ident {
+            #(#variant_idents,) *
+        }
+        impl #enum_ident {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    #(#enum_ident:: #variant_idents => #variant_values,) *
+                }
+            }
+        }
+        impl From < #enum_ident > for PrimField < String > {
+            fn from(v: #enum_ident) -> Self {
+                v.as_str().into()
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Adds a setter that sets a write-only field along with its companion version
+/// field, which must be changed to make Terraform send a new value.
+pub fn generate_write_only_setter(
+    out: &mut TopLevelFields,
+    k: &str,
+    value_type: TokenStream,
+    version_k: &str,
+    version_type: TokenStream,
+    self_has_identity: bool,
+) {
+    let field_name = format_ident!("{}", sanitize(k).1);
+    let version_field_name = format_ident!("{}", sanitize(version_k).1);
+    let set_field_name = format_ident!("set_{}_with_version", k);
+    let set_doc =
+        format!(
+            "Set the write-only field `{}` and `{}`. Change the version to make Terraform update the value.",
+            field_name,
+            version_field_name
+        );
+    let (pat_mut_self, access_mut_self, must_use) = mut_self(self_has_identity);
+    out.mut_methods.push(quote!(#[doc = #set_doc] #must_use pub fn #set_field_name(
+        #pat_mut_self,
+        v: impl Into < #value_type >,
+        version: impl Into < #version_type >
+    ) -> Self {
+        #access_mut_self.#field_name = Some(v.into());
+        #access_mut_self.#version_field_name = Some(version.into());
+        self
+    }));
+}
+
 fn generate_type(
+    ctx: &GenerateContext,
+    attr_path: &str,
     extra_types: &mut Vec<TokenStream>,
-    path: &Vec<String>,
+    path: &[String],
     at: (Option<&ValueSchema>, Option<&ValueSchemaNested>),
-) -> (TokenStream, Option<(TokenStream, TokenStream)>) {
-    match at {
+) -> Result<(TokenStream, Option<(TokenStream, TokenStream)>), loga::Error> {
+    Ok(match at {
         (Some(ValueSchema::Simple(t)), None) => generate_simple_type(t),
-        (Some(ValueSchema::AggColl(at)), None) => generate_agg_type_coll(extra_types, path, at.as_ref()),
-        (Some(ValueSchema::AggObj(at)), None) => generate_agg_type_obj(extra_types, path, at.as_ref()),
+        (Some(ValueSchema::AggColl(at)), None) => generate_agg_type_coll(
+            ctx,
+            attr_path,
+            extra_types,
+            path,
+            at.as_ref(),
+        )?,
+        (Some(ValueSchema::AggObj(at)), None) => generate_agg_type_obj(ctx, attr_path, extra_types, path, at.as_ref())?,
         (None, Some(x)) => match x.nesting_mode {
-            super::sourceschema::NestingMode::List => {
+            terrars::sourceschema::NestingMode::List => {
                 let (element_type, element_ref_type) =
-                    generate_agg_type_obj_nested(extra_types, &add_path(&path, "el"), &x.attributes);
+                    generate_agg_type_obj_nested(
+                        ctx,
+                        attr_path,
+                        extra_types,
+                        &add_path(path, "el"),
+                        &x.attributes,
+                    )?;
                 (
                     quote!(Vec < #element_type >),
                     element_ref_type.map(|(_, r2)| (quote!(ListRef), quote!(ListRef < #r2 >))),
                 )
             },
-            super::sourceschema::NestingMode::Set => {
+            terrars::sourceschema::NestingMode::Set => {
                 let (element_type, element_ref_type) =
-                    generate_agg_type_obj_nested(extra_types, &add_path(&path, "el"), &x.attributes);
+                    generate_agg_type_obj_nested(
+                        ctx,
+                        attr_path,
+                        extra_types,
+                        &add_path(path, "el"),
+                        &x.attributes,
+                    )?;
                 (
                     quote!(Vec < #element_type >),
                     element_ref_type.map(|(_, r2)| (quote!(SetRef), quote!(SetRef < #r2 >))),
                 )
             },
-            super::sourceschema::NestingMode::Single => {
+            terrars::sourceschema::NestingMode::Single | terrars::sourceschema::NestingMode::Group => {
                 let (element_type, element_ref_type) =
-                    generate_agg_type_obj_nested(extra_types, path, &x.attributes);
+                    generate_agg_type_obj_nested(ctx, attr_path, extra_types, path, &x.attributes)?;
                 (element_type, element_ref_type)
             },
+            terrars::sourceschema::NestingMode::Map => {
+                let (element_type, element_ref_type) =
+                    generate_agg_type_obj_nested(
+                        ctx,
+                        attr_path,
+                        extra_types,
+                        &add_path(path, "el"),
+                        &x.attributes,
+                    )?;
+                (
+                    quote!(RecField < #element_type >),
+                    element_ref_type.map(|(_, r2)| (quote!(RecRef), quote!(RecRef < #r2 >))),
+                )
+            },
         },
-        (None, None) | (Some(_), Some(_)) => unreachable!(),
-    }
+        (None, None) | (Some(_), Some(_)) => {
+            return Err(
+                loga::err_with(
+                    "Attribute must have exactly one of `type` and `nested_type`",
+                    ea!(attribute = attr_path),
+                ),
+            );
+        },
+    })
 }
 
 pub fn generate_agg_type_obj(
+    ctx: &GenerateContext,
+    attr_path: &str,
     extra_types: &mut Vec<TokenStream>,
-    path: &Vec<String>,
+    path: &[String],
     at: &AggObjType,
-) -> (TokenStream, Option<(TokenStream, TokenStream)>) {
+) -> Result<(TokenStream, Option<(TokenStream, TokenStream)>), loga::Error> {
     let mut raw_fields = TopLevelFields::default();
-    generate_fields_from_valueschema_map(&mut raw_fields, &path, &at.1, false);
+    generate_fields_from_valueschema_map(ctx, attr_path, &mut raw_fields, path, &at.1, false)?;
     let (rust_type, rust_ref_type) = generate_nonident_rust_type(extra_types, path, raw_fields);
-    (rust_type, Some((rust_ref_type.clone(), rust_ref_type)))
+    Ok((rust_type, Some((rust_ref_type.clone(), rust_ref_type))))
 }
 
 pub fn generate_agg_type_obj_nested(
+    ctx: &GenerateContext,
+    attr_path: &str,
     extra_types: &mut Vec<TokenStream>,
-    path: &Vec<String>,
+    path: &[String],
     at: &BTreeMap<String, Value>,
-) -> (TokenStream, Option<(TokenStream, TokenStream)>) {
+) -> Result<(TokenStream, Option<(TokenStream, TokenStream)>), loga::Error> {
     let mut raw_fields = TopLevelFields::default();
-    generate_fields_from_value_map(&mut raw_fields, &path, &at, false);
+    generate_fields_from_value_map(ctx, attr_path, &mut raw_fields, path, at, false)?;
     let (rust_type, rust_ref_type) = generate_nonident_rust_type(extra_types, path, raw_fields);
-    (rust_type, Some((rust_ref_type.clone(), rust_ref_type)))
+    Ok((rust_type, Some((rust_ref_type.clone(), rust_ref_type))))
 }
 
 fn generate_agg_type_coll(
+    ctx: &GenerateContext,
+    attr_path: &str,
     extra_types: &mut Vec<TokenStream>,
-    path: &Vec<String>,
+    path: &[String],
     at: &AggCollType,
-) -> (TokenStream, Option<(TokenStream, TokenStream)>) {
-    match at.0 {
+) -> Result<(TokenStream, Option<(TokenStream, TokenStream)>), loga::Error> {
+    Ok(match at.0 {
         AggCollTypeKey::List => {
             let (element_type, element_ref_type) = match &at.1 {
-                ValueSchema::Simple(t) => generate_simple_type(&t),
-                ValueSchema::AggColl(a) => generate_agg_type_coll(extra_types, &add_path(&path, "el"), a.as_ref()),
-                ValueSchema::AggObj(a) => generate_agg_type_obj(extra_types, &add_path(&path, "el"), a.as_ref()),
+                ValueSchema::Simple(t) => generate_simple_type(t),
+                ValueSchema::AggColl(a) => generate_agg_type_coll(
+                    ctx,
+                    attr_path,
+                    extra_types,
+                    &add_path(path, "el"),
+                    a.as_ref(),
+                )?,
+                ValueSchema::AggObj(a) => generate_agg_type_obj(
+                    ctx,
+                    attr_path,
+                    extra_types,
+                    &add_path(path, "el"),
+                    a.as_ref(),
+                )?,
             };
             (
                 quote!(ListField < #element_type >),
@@ -327,9 +636,21 @@ fn generate_agg_type_coll(
         },
         AggCollTypeKey::Set => {
             let (element_type, element_ref_type) = match &at.1 {
-                ValueSchema::Simple(t) => generate_simple_type(&t),
-                ValueSchema::AggColl(a) => generate_agg_type_coll(extra_types, &add_path(&path, "el"), a.as_ref()),
-                ValueSchema::AggObj(a) => generate_agg_type_obj(extra_types, &add_path(&path, "el"), a.as_ref()),
+                ValueSchema::Simple(t) => generate_simple_type(t),
+                ValueSchema::AggColl(a) => generate_agg_type_coll(
+                    ctx,
+                    attr_path,
+                    extra_types,
+                    &add_path(path, "el"),
+                    a.as_ref(),
+                )?,
+                ValueSchema::AggObj(a) => generate_agg_type_obj(
+                    ctx,
+                    attr_path,
+                    extra_types,
+                    &add_path(path, "el"),
+                    a.as_ref(),
+                )?,
             };
             (
                 quote!(SetField < #element_type >),
@@ -338,41 +659,54 @@ fn generate_agg_type_coll(
         },
         AggCollTypeKey::Rec => {
             let (element_type, element_ref_type) = match &at.1 {
-                ValueSchema::Simple(t) => generate_simple_type(&t),
-                ValueSchema::AggColl(_) => {
-                    panic!("supposedly not supported by terraform")
-                },
-                ValueSchema::AggObj(_) => {
-                    panic!("supposedly not supported by terraform")
-                },
+                ValueSchema::Simple(t) => generate_simple_type(t),
+                ValueSchema::AggColl(a) => generate_agg_type_coll(
+                    ctx,
+                    attr_path,
+                    extra_types,
+                    &add_path(path, "el"),
+                    a.as_ref(),
+                )?,
+                ValueSchema::AggObj(a) => generate_agg_type_obj(
+                    ctx,
+                    attr_path,
+                    extra_types,
+                    &add_path(path, "el"),
+                    a.as_ref(),
+                )?,
             };
             (
                 quote!(RecField < #element_type >),
                 element_ref_type.map(|(_, r2)| (quote!(RecRef), quote!(RecRef < #r2 >))),
             )
         },
-    }
+    })
 }
 
 fn generate_block_agg_obj(
+    ctx: &GenerateContext,
+    attr_path: &str,
     superout: &mut TopLevelFields,
-    path: &Vec<String>,
+    path: &[String],
     obj: &Block,
-) -> (TokenStream, TokenStream) {
+) -> Result<(TokenStream, TokenStream), loga::Error> {
     let mut raw_fields = TopLevelFields::default();
-    generate_fields_from_value_map(&mut raw_fields, &path, &obj.attributes, false);
-    generate_block_fields(&mut raw_fields, &path, &obj.block_types, false);
-    generate_nonident_rust_type(&mut superout.extra_types, path, raw_fields)
+    generate_fields_from_value_map(ctx, attr_path, &mut raw_fields, path, &obj.attributes, false)?;
+    generate_block_fields(ctx, attr_path, &mut raw_fields, path, &obj.block_types, false)?;
+    Ok(generate_nonident_rust_type(&mut superout.extra_types, path, raw_fields))
 }
 
 pub fn generate_block_fields(
+    ctx: &GenerateContext,
+    attr_path: &str,
     out: &mut TopLevelFields,
-    path: &Vec<String>,
+    path: &[String],
     fields: &BTreeMap<String, NestedBlock>,
     self_has_identity: bool,
-) {
+) -> Result<(), loga::Error> {
     for (k, v) in fields {
-        let mut path = path.clone();
+        let attr_path = add_attr_path(attr_path, k);
+        let mut path = path.to_vec();
         path.extend(k.split("_").map(ToString::to_string));
         let rust_type;
         let rust_ref_type;
@@ -380,24 +714,32 @@ pub fn generate_block_fields(
         match v.nesting_mode {
             NestingMode::List => {
                 let (element_type, element_ref_type) =
-                    generate_block_agg_obj(out, &add_path(&path, "el"), &v.block);
+                    generate_block_agg_obj(ctx, &attr_path, out, &add_path(&path, "el"), &v.block)?;
                 rust_type = quote!(Vec < #element_type >);
                 rust_ref_type = Some((quote!(ListRef), quote!(ListRef < #element_ref_type >)));
                 block_type = Some(element_type);
             },
             NestingMode::Set => {
-                let (element_type, _) = generate_block_agg_obj(out, &add_path(&path, "el"), &v.block);
+                let (element_type, element_ref_type) =
+                    generate_block_agg_obj(ctx, &attr_path, out, &add_path(&path, "el"), &v.block)?;
                 rust_type = quote!(Vec < #element_type >);
-                rust_ref_type = None;
+                rust_ref_type = Some((quote!(SetRef), quote!(SetRef < #element_ref_type >)));
                 block_type = Some(element_type);
             },
-            NestingMode::Single => {
+            NestingMode::Single | NestingMode::Group => {
                 let (element_type, element_ref_type) =
-                    generate_block_agg_obj(out, &add_path(&path, "el"), &v.block);
+                    generate_block_agg_obj(ctx, &attr_path, out, &add_path(&path, "el"), &v.block)?;
                 rust_type = element_type;
                 rust_ref_type = Some((element_ref_type.clone(), element_ref_type));
                 block_type = None;
             },
+            NestingMode::Map => {
+                let (element_type, element_ref_type) =
+                    generate_block_agg_obj(ctx, &attr_path, out, &add_path(&path, "el"), &v.block)?;
+                rust_type = quote!(RecField < #element_type >);
+                rust_ref_type = Some((quote!(RecRef), quote!(RecRef < #element_ref_type >)));
+                block_type = None;
+            },
         };
         generate_field(
             out,
@@ -405,66 +747,142 @@ pub fn generate_block_fields(
             rust_type,
             rust_ref_type,
             "",
-            super::sourceschema::ValueBehaviorHelper::UserOptional,
+            terrars::sourceschema::ValueBehaviorHelper::UserOptional,
             self_has_identity,
             block_type,
         );
     }
+    Ok(())
 }
 
 pub fn generate_fields_from_value_map(
+    ctx: &GenerateContext,
+    attr_path: &str,
     out: &mut TopLevelFields,
-    path: &Vec<String>,
+    path: &[String],
     fields: &BTreeMap<String, Value>,
     self_has_identity: bool,
-) {
+) -> Result<(), loga::Error> {
     for (k, v) in fields {
-        let mut path = path.clone();
+        let attr_path = add_attr_path(attr_path, k);
+        let mut path = path.to_vec();
         path.extend(k.split("_").map(ToString::to_string));
+        let behavior = v.behavior().context_with("Error generating attribute", ea!(attribute = attr_path))?;
         let (rust_field_type, rust_field_ref_type) =
-            generate_type(&mut out.extra_types, &path, (v.r#type.as_ref(), v.nested_type.as_ref()));
+            generate_type(
+                ctx,
+                &attr_path,
+                &mut out.extra_types,
+                &path,
+                (v.r#type.as_ref(), v.nested_type.as_ref()),
+            )?;
+        if matches!(v.r#type, Some(ValueSchema::Simple(ScalarTypeKey::String))) &&
+            ctx.json_attributes.contains(&attr_path) {
+            generate_json_setter(out, k, &behavior, self_has_identity);
+        }
+        if matches!(v.r#type, Some(ValueSchema::Simple(ScalarTypeKey::String))) {
+            generate_formatted_setters(ctx, out, &attr_path, k, &behavior, self_has_identity);
+        }
+        if let (Some(ValueSchema::Simple(ScalarTypeKey::String)), Some(values)) =
+            (&v.r#type, ctx.enum_attributes.get(&attr_path)) {
+            generate_enum_type(out, &attr_path, &path, values)?;
+        }
+        let mut doc = v.description.clone().unwrap_or_else(String::new);
+        if v.sensitive {
+            doc =
+                format!(
+                    "{}\n\n# Sensitive\n\nThe provider marks this as sensitive. Terraform hides it in plan output, but avoid logging it yourself.",
+                    doc
+                );
+        }
+        let mut rust_field_ref_type = rust_field_ref_type;
+        if v.write_only {
+            // Not stored in the state, so can't be referenced
+            rust_field_ref_type = None;
+            doc = format!("{}\n\nThis is write-only: it isn't stored in the state and can't be referenced.", doc);
+            let version_k = format!("{}_version", k);
+            if let (
+                ValueBehaviorHelper::UserOptional | ValueBehaviorHelper::UserOptionalComputed,
+                Some(
+                    version @ Value { r#type: Some(ValueSchema::Simple(version_type)), .. },
+                ),
+            ) = (&behavior, fields.get(&version_k)) {
+                if matches!(
+                    version.behavior(),
+                    Ok(ValueBehaviorHelper::UserOptional | ValueBehaviorHelper::UserOptionalComputed)
+                ) {
+                    generate_write_only_setter(
+                        out,
+                        k,
+                        rust_field_type.clone(),
+                        &version_k,
+                        generate_simple_type(version_type).0,
+                        self_has_identity,
+                    );
+                }
+            }
+        }
         generate_field(
             out,
             k,
             rust_field_type,
             rust_field_ref_type,
-            &v.description.as_ref().map(|s| s.clone()).unwrap_or_else(String::new),
-            v.behavior(),
+            &doc,
+            behavior,
             self_has_identity,
             None,
         );
     }
+    Ok(())
 }
 
 pub fn generate_fields_from_valueschema_map(
+    ctx: &GenerateContext,
+    attr_path: &str,
     out: &mut TopLevelFields,
-    path: &Vec<String>,
+    path: &[String],
     fields: &BTreeMap<String, ValueSchema>,
     self_has_identity: bool,
-) {
+) -> Result<(), loga::Error> {
     for (k, v) in fields {
-        let mut path = path.clone();
+        let attr_path = add_attr_path(attr_path, k);
+        let mut path = path.to_vec();
         path.extend(k.split("_").map(ToString::to_string));
-        let (rust_field_type, rust_field_ref_type) = generate_type(&mut out.extra_types, &path, (Some(v), None));
+        let (rust_field_type, rust_field_ref_type) =
+            generate_type(ctx, &attr_path, &mut out.extra_types, &path, (Some(v), None))?;
+        if matches!(v, ValueSchema::Simple(ScalarTypeKey::String)) && ctx.json_attributes.contains(&attr_path) {
+            generate_json_setter(out, k, &ValueBehaviorHelper::UserOptional, self_has_identity);
+        }
+        if matches!(v, ValueSchema::Simple(ScalarTypeKey::String)) {
+            generate_formatted_setters(
+                ctx,
+                out,
+                &attr_path,
+                k,
+                &ValueBehaviorHelper::UserOptional,
+                self_has_identity,
+            );
+        }
         generate_field(
             out,
             k,
             rust_field_type,
             rust_field_ref_type,
             "",
-            super::sourceschema::ValueBehaviorHelper::UserOptional,
+            terrars::sourceschema::ValueBehaviorHelper::UserOptional,
             self_has_identity,
             None,
         );
     }
+    Ok(())
 }
 
 pub fn generate_nonident_rust_type(
     extra_types: &mut Vec<TokenStream>,
-    path: &Vec<String>,
+    path: &[String],
     mut raw_fields: TopLevelFields,
 ) -> (TokenStream, TokenStream) {
-    let camel_name = to_camel(&path);
+    let camel_name = to_camel(path);
     raw_fields.finish(&camel_name);
     let builder_fields = raw_fields.builder_fields;
     let copy_builder_fields = raw_fields.copy_builder_fields;
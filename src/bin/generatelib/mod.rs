@@ -1,3 +1,2 @@
 pub mod errextra;
 pub mod generate;
-pub mod sourceschema;
@@ -22,17 +22,25 @@ use serde::{
     Deserialize,
 };
 use serde_json::json;
-use terrars::get_terraform_binary;
+use terrars::{
+    get_terraform_binary,
+    sourceschema::ProviderSchemas,
+};
 use std::{
-    collections::HashSet,
+    collections::{
+        BTreeMap,
+        HashSet,
+    },
     fs::{
         self,
         create_dir_all,
         File,
-        remove_dir_all,
+        remove_file,
+        remove_dir,
     },
     io::Write,
     path::{
+        Component,
         Path,
         PathBuf,
     },
@@ -42,15 +50,28 @@ use crate::generatelib::{
     generate::{
         to_camel,
         to_snake,
+        add_attr_path,
+        module_name,
         TopLevelFields,
+        GenerateContext,
         generate_fields_from_value_map,
         generate_block_fields,
     },
-    sourceschema::ProviderSchemas,
 };
 
 pub mod generatelib;
 
+/// Lists the files written by the last generation, so they can be replaced without
+/// touching other files in the destination directory.
+const MANIFEST_NAME: &str = ".terrars-manifest";
+
+/// The lockfile `terraform init` writes.
+const LOCKFILE_NAME: &str = ".terraform.lock.hcl";
+
+/// Lists the Cargo features added by the last generation, so they can be updated
+/// without touching user-defined features in the same `Cargo.toml`.
+const FEATURES_MANIFEST_NAME: &str = ".terrars-features";
+
 pub trait CollCommand {
     fn run(&mut self) -> Result<(), loga::Error>;
 }
@@ -71,16 +92,168 @@ impl CollCommand for Command {
     }
 }
 
+/// How to arrange the generated resource, datasource, and ephemeral resource
+/// modules.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+enum Layout {
+    /// Every module directly in the provider module, with everything re-exported from
+    /// it (ex: `aws::S3Bucket`).
+    #[default]
+    Flat,
+    /// Modules grouped by the first part of the name (ex: `aws::s3::S3Bucket`,
+    /// `aws::s3::DataS3Bucket`). Datasources and ephemeral resources keep their prefix
+    /// within the group (ex: `s3/data_bucket.rs`) and names with a single part are
+    /// placed in a group of the same name (ex: `vpc/vpc.rs`).
+    Nested,
+}
+
+/// The parts of generated code that differ between datasources and ephemeral
+/// resources, which are otherwise generated the same way.
+struct ReadBlockKind {
+    /// Prefix of the Terraform address and of the generated module and type names
+    prefix: &'static str,
+    /// Name used in messages and docs
+    desc: &'static str,
+    article: &'static str,
+    /// What Terraform does with the block, present and past tense, for docs
+    verb: (&'static str, &'static str),
+    /// Name of the `terrars` trait implemented by the block type (`Datasource` has
+    /// `Datasource_`, `extract_datasource_type`, `Stack::add_datasource`, etc.)
+    trait_name: &'static str,
+}
+
+const DATASOURCE_KIND: ReadBlockKind = ReadBlockKind {
+    prefix: "data",
+    desc: "datasource",
+    article: "a",
+    verb: ("read", "read"),
+    trait_name: "Datasource",
+};
+
+const EPHEMERAL_KIND: ReadBlockKind = ReadBlockKind {
+    prefix: "ephemeral",
+    desc: "ephemeral resource",
+    article: "an",
+    verb: ("open", "opened"),
+    trait_name: "Ephemeral",
+};
+
+/// Returns the group (if nested) and module name for generated code. `kind` is the
+/// `data`/`ephemeral` prefix, and `name_parts` is the name without the provider
+/// prefix.
+fn module_location(layout: Layout, kind: Option<&str>, name_parts: &[String]) -> (Option<String>, String) {
+    let with_kind = |parts: &[String]| {
+        module_name(to_snake(&kind.into_iter().map(ToString::to_string).chain(parts.iter().cloned()).collect::<Vec<_>>()))
+    };
+    match layout {
+        Layout::Flat => (None, with_kind(name_parts)),
+        Layout::Nested => {
+            let rest = if name_parts.len() > 1 {
+                &name_parts[1..]
+            } else {
+                &name_parts[..1]
+            };
+            (Some(module_name(name_parts[0].clone())), with_kind(rest))
+        },
+    }
+}
+
+/// Read the names of the files listed in the manifest in `dir`, if any. The
+/// manifest is only trusted to name files inside `dir`: absolute paths and `..`
+/// components are rejected.
+fn read_manifest(dir: &Path) -> Result<Vec<String>, loga::Error> {
+    let manifest_path = dir.join(MANIFEST_NAME);
+    if !manifest_path.exists() {
+        return Ok(vec![]);
+    }
+    let mut out = vec![];
+    for name in fs::read_to_string(
+        &manifest_path,
+    ).context_with(
+        "Error reading manifest of previously generated files",
+        ea!(path = manifest_path.to_string_lossy()),
+    )?.lines() {
+        if name.is_empty() {
+            continue;
+        }
+        if !Path::new(name).components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(
+                loga::err_with(
+                    "Manifest of previously generated files contains a path outside the destination directory",
+                    ea!(path = manifest_path.to_string_lossy(), entry = name),
+                ),
+            );
+        }
+        out.push(name.to_string());
+    }
+    return Ok(out);
+}
+
+/// Compare freshly generated files in `generated_dir` with a previous generation
+/// in `dest`, returning the paths in `dest` that differ, are missing, or are no
+/// longer generated.
+fn compare_generated(generated_dir: &Path, dest: &Path) -> Result<Vec<PathBuf>, loga::Error> {
+    fn read(path: &Path) -> Result<Option<Vec<u8>>, loga::Error> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        return Ok(Some(fs::read(path).context_with("Error reading generated file", ea!(path = path.to_string_lossy()))?));
+    }
+
+    let mut names = vec![MANIFEST_NAME.to_string(), FEATURES_MANIFEST_NAME.to_string()];
+    for dir in [generated_dir, dest] {
+        names.extend(read_manifest(dir)?);
+    }
+    names.sort();
+    names.dedup();
+    let mut out = vec![];
+    for name in names {
+        if read(&generated_dir.join(&name))? != read(&dest.join(&name))? {
+            out.push(dest.join(&name));
+        }
+    }
+    return Ok(out);
+}
+
 fn main() {
     match es!({
         #[derive(Serialize, Deserialize)]
         struct Config {
             provider: String,
             version: String,
+            /// Version constraint to put in the generated `required_providers` (ex: `~>
+            /// 5.0`). Defaults to exactly `version`, which is always the version used to
+            /// generate the bindings.
+            version_constraint: Option<String>,
             include: Option<Vec<String>>,
             exclude: Option<Vec<String>>,
             dest: PathBuf,
             feature_gate: Option<PathBuf>,
+            /// String attributes containing JSON, as dotted paths starting with the
+            /// resource/datasource name (ex: `iam_role.assume_role_policy`). These get an
+            /// additional `set_*_json` setter that takes any serializable value.
+            json: Option<Vec<String>>,
+            /// String attributes containing durations (ex: `30s`), as paths like `json`.
+            /// These get an additional `set_*_duration` setter that takes a
+            /// `std::time::Duration`.
+            duration: Option<Vec<String>>,
+            /// String attributes containing sizes (ex: `10Gi`), as paths like `json`.
+            /// These get an additional `set_*_size` setter that takes a number of bytes.
+            size: Option<Vec<String>>,
+            /// How to arrange the generated modules: `flat` (default) or `nested`.
+            layout: Option<Layout>,
+            /// Only generate the provider, skipping all resources, datasources, and
+            /// ephemeral resources.
+            provider_only: Option<bool>,
+            /// Copy the `.terraform.lock.hcl` Terraform wrote when getting the schema into
+            /// `dest`, as a starting point for a lockfile matching the generated bindings.
+            /// It only has the hashes for the current platform's provider package.
+            lockfile: Option<bool>,
+            /// Allowed values for provider attributes, by attribute name (ex: `{"region":
+            /// ["us-east-1", "eu-west-1"]}`). Each gets an enum that can be passed where
+            /// the attribute's value is expected.
+            enums: Option<BTreeMap<String, Vec<String>>>,
         }
 
         #[derive(Aargvark)]
@@ -89,58 +262,95 @@ fn main() {
             configs: Vec<AargvarkJson<Config>>,
             /// Save the provider json in this dir (debug helper).
             dump: Option<()>,
+            /// Read the provider schema json from this file (ex: one saved with `dump`)
+            /// instead of getting it from terraform, for all configs (debug helper).
+            schema: Option<PathBuf>,
+            /// Don't write anything, instead generate into a temporary directory and fail
+            /// if the result differs from the files in `dest` (ex: to check in CI that
+            /// committed bindings are up to date).
+            check: Option<()>,
+            /// Only check that the provider version can be installed and its schema
+            /// parsed, without generating anything (ex: to quickly check provider pins in
+            /// CI).
+            providers_lock_only: Option<()>,
         }
 
         let args = vark::<Arguments>();
         if args.configs.is_empty() {
             return Err(loga::err("No configs specified; nothing to do"));
         }
+        let mut out_of_date = vec![];
         for config in args.configs {
             let config = config.value;
             let (vendor, shortname) =
-                config.provider.split_once("/").unwrap_or_else(|| ("hashicorp".into(), &config.provider));
+                config.provider.split_once("/").unwrap_or_else(|| ("hashicorp", &config.provider));
             let provider_prefix = format!("{}_", shortname);
             let mut include: HashSet<&String> = config.include.iter().flatten().collect();
             let mut exclude: HashSet<&String> = config.exclude.iter().flatten().collect();
             let whitelist = !include.is_empty();
+            let layout = config.layout.unwrap_or_default();
+            let ctx = GenerateContext {
+                json_attributes: config.json.iter().flatten().cloned().collect(),
+                duration_attributes: config.duration.iter().flatten().cloned().collect(),
+                size_attributes: config.size.iter().flatten().cloned().collect(),
+                enum_attributes: config
+                    .enums
+                    .iter()
+                    .flatten()
+                    .map(|(k, v)| (add_attr_path("provider", k), v.clone()))
+                    .collect(),
+            };
 
             // Feature output
             let mut features = vec![];
+            let mut resource_features = vec![];
 
             // Get provider schema
             let dir = tempfile::tempdir()?;
-            fs::write(dir.path().join("providers.tf.json"), &serde_json::to_vec(&json!({
-                "terraform": {
-                    "required_providers": {
-                        shortname: {
-                            "source": config.provider,
-                            "version": config.version,
+            let mut schema: ProviderSchemas = if let Some(schema_path) = &args.schema {
+                if config.lockfile.unwrap_or(false) {
+                    return Err(loga::err("Can't copy the lockfile when reading the provider schema from a file"));
+                }
+                serde_json::from_slice(
+                    &fs::read(
+                        schema_path,
+                    ).context_with("Error reading provider schema json", ea!(path = schema_path.to_string_lossy()))?,
+                ).context_with("Error parsing provider schema json", ea!(path = schema_path.to_string_lossy()))?
+            } else {
+                fs::write(dir.path().join("providers.tf.json"), serde_json::to_vec(&json!({
+                    "terraform": {
+                        "required_providers": {
+                            shortname: {
+                                "source": config.provider,
+                                "version": config.version,
+                            }
                         }
                     }
-                }
-            })).unwrap()).context("Failed to write bootstrap terraform code for provider schema extraction")?;
-            Command::new(get_terraform_binary())
-                .args(&["init", "-no-color"])
-                .current_dir(&dir)
-                .run()
-                .context("Error initializing terraform in export dir")?;
-            let schema_raw =
+                })).unwrap()).context("Failed to write bootstrap terraform code for provider schema extraction")?;
                 Command::new(get_terraform_binary())
-                    .args(&["providers", "schema", "-json", "-no-color"])
+                    .args(["init", "-no-color"])
                     .current_dir(&dir)
-                    .output()
-                    .context("Error outputting terraform provider schema")?
-                    .stdout;
-            if args.dump.is_some() {
-                fs::write("dump.json", &schema_raw)?;
-            }
-            let schema: ProviderSchemas =
-                serde_json::from_slice(&schema_raw).context("Error parsing provider schema json from terraform")?;
+                    .run()
+                    .context("Error initializing terraform in export dir")?;
+                // Scoped to this block so the raw json (tens of MB for large providers) is
+                // freed before generating
+                let schema_raw =
+                    Command::new(get_terraform_binary())
+                        .args(["providers", "schema", "-json", "-no-color"])
+                        .current_dir(&dir)
+                        .output()
+                        .context("Error outputting terraform provider schema")?
+                        .stdout;
+                if args.dump.is_some() {
+                    fs::write("dump.json", &schema_raw)?;
+                }
+                serde_json::from_slice(&schema_raw).context("Error parsing provider schema json from terraform")?
+            };
 
             // Generate
             fn write_file(path: &Path, contents: Vec<TokenStream>) -> Result<(), loga::Error> {
                 es!({
-                    File::create(&path)
+                    File::create(path)
                         .context("Failed to create rust file")?
                         .write_all(
                             genemichaels_lib::format_ast(
@@ -187,32 +397,103 @@ fn main() {
             // Provider type + provider
             let provider_schema = {
                 let key = format!("registry.terraform.io/{}/{}", vendor, shortname);
-                schema
-                    .provider_schemas
-                    .get(&key)
-                    .context_with("Missing provider schema for listed provider", ea!(provider = config.provider))?
+                if schema.provider_schemas.is_empty() {
+                    return Err(
+                        loga::err_with(
+                            "Terraform returned no provider schemas; check that the provider and version exist",
+                            ea!(provider = config.provider, version = config.version),
+                        ),
+                    );
+                }
+                match schema.provider_schemas.remove(&key) {
+                    Some(s) => s,
+                    None => {
+                        return Err(
+                            loga::err_with(
+                                "Missing provider schema for listed provider",
+                                ea!(
+                                    provider = config.provider,
+                                    expected = key,
+                                    available = schema.provider_schemas.keys().cloned().collect::<Vec<_>>().join(", ")
+                                ),
+                            ),
+                        );
+                    },
+                }
             };
+            drop(schema);
+            if args.providers_lock_only.is_some() {
+                println!("Provider {} {} schema ok", config.provider, config.version);
+                continue;
+            }
             let provider_name_parts = &shortname.split("-").map(ToString::to_string).collect::<Vec<String>>();
-            let provider_dir = config.dest;
-            if provider_dir.exists() {
-                remove_dir_all(&provider_dir)?;
+            let check_dir;
+            let provider_dir = if args.check.is_some() {
+                check_dir = tempfile::tempdir()?;
+                check_dir.path().to_path_buf()
+            } else {
+                config.dest.clone()
+            };
+
+            // Only remove files generated by a previous run, leaving any hand-written files
+            // in the directory alone
+            let manifest_path = provider_dir.join(MANIFEST_NAME);
+            let mut group_dirs = HashSet::new();
+            for name in read_manifest(&provider_dir)? {
+                let path = provider_dir.join(name);
+                if path.exists() {
+                    remove_file(
+                        &path,
+                    ).context_with("Error removing previously generated file", ea!(path = path.to_string_lossy()))?;
+                }
+                if let Some(parent) = path.parent() {
+                    if parent != provider_dir {
+                        group_dirs.insert(parent.to_path_buf());
+                    }
+                }
+            }
+
+            // Remove group directories from the nested layout if nothing else is in them
+            for dir in group_dirs {
+                if dir.exists() && fs::read_dir(&dir)?.next().is_none() {
+                    remove_dir(
+                        &dir,
+                    ).context_with(
+                        "Error removing previously generated directory",
+                        ea!(path = dir.to_string_lossy()),
+                    )?;
+                }
             }
             create_dir_all(&provider_dir)?;
+            let mut generated = vec![];
             let mut mod_out = vec![];
+            let mut group_mod_out: BTreeMap<String, Vec<TokenStream>> = BTreeMap::new();
             let provider_ident: Ident;
             {
                 let mut out = rustfile_template();
                 let camel_name = to_camel(provider_name_parts);
                 let source = &config.provider;
                 let version = &config.version;
+                let version_constraint = config.version_constraint.as_ref().unwrap_or(&config.version);
                 let provider_inner_mut_ident = format_ident!("Provider{}Data", camel_name);
                 let mut raw_fields = TopLevelFields::default();
                 generate_fields_from_value_map(
+                    &ctx,
+                    "provider",
                     &mut raw_fields,
-                    &provider_name_parts,
+                    provider_name_parts,
                     &provider_schema.provider.block.attributes,
                     true,
-                );
+                ).context_with("Error generating provider", ea!(provider = config.provider))?;
+                generate_block_fields(
+                    &ctx,
+                    "provider",
+                    &mut raw_fields,
+                    provider_name_parts,
+                    &provider_schema.provider.block.block_types,
+                    true,
+                ).context_with("Error generating provider", ea!(provider = config.provider))?;
+                raw_fields.finish(&format!("Provider{}", camel_name));
                 let builder_fields = raw_fields.builder_fields;
                 let copy_builder_fields = raw_fields.copy_builder_fields;
                 let extra_types = raw_fields.extra_types;
@@ -224,7 +505,8 @@ fn main() {
                 out.push(quote!{
                     #[derive(Serialize)] struct #provider_inner_mut_ident {
                         #[serde(skip_serializing_if = "Option::is_none")] alias: Option < String >,
-                        #(#provider_fields,) *
+                        #(#provider_fields,) * #[serde(flatten)] __terrars_raw: std:: collections:: BTreeMap < String,
+                        AnyField >,
                     }
                     struct #provider_inner_ident {
                         data: RefCell < #provider_inner_mut_ident >,
@@ -244,6 +526,14 @@ fn main() {
                             self.0.data.borrow_mut().alias = Some(alias.to_string());
                             self
                         }
+                        #[doc = "Set an arbitrary attribute or block, for settings missing from the schema the bindings were generated from. This bypasses type checking and replaces any value set with the typed setters."] pub fn set_raw_block(
+                            self,
+                            k: impl ToString,
+                            v: impl Into<AnyField>
+                        ) -> Self {
+                            self.0.data.borrow_mut().__terrars_raw.insert(k.to_string(), v.into());
+                            self
+                        }
                         #(#provider_mut_methods) *
                     }
                     impl Provider for #provider_inner_ident {
@@ -253,9 +543,12 @@ fn main() {
                         fn extract_provider_type(&self) -> serde_json::Value {
                             serde_json::json!({
                                 "source": #source,
-                                "version": #version,
+                                "version": #version_constraint,
                             })
                         }
+                        fn extract_schema_version(&self) -> Option<String> {
+                            Some(#version.into())
+                        }
                         fn extract_provider(&self) -> serde_json::Value {
                             serde_json::to_value(&self.data).unwrap()
                         }
@@ -268,7 +561,7 @@ fn main() {
                             let out = #provider_ident(Rc:: new(#provider_inner_ident {
                                 data: RefCell:: new(#provider_inner_mut_ident {
                                     alias: None,
-                                    #(#copy_builder_fields,) *
+                                    #(#copy_builder_fields,) * __terrars_raw: Default:: default(),
                                 }),
                             }));
                             stack.add_provider(out.0.clone());
@@ -277,15 +570,34 @@ fn main() {
                     }
                     #(#extra_types) *
                 });
-                write_file(&provider_dir.join("provider.rs"), out)?;
+                write_file(
+                    &provider_dir.join("provider.rs"),
+                    out,
+                ).context_with("Error writing provider", ea!(provider = config.provider))?;
+                generated.push("provider.rs".to_string());
                 let path_ident = format_ident!("provider");
                 mod_out.push(quote!(pub mod #path_ident; pub use #path_ident::*;));
             }
 
+            // Skip everything but the provider if requested. The schemas are consumed as
+            // they're generated to free memory early.
+            let (resource_schemas, data_source_schemas, ephemeral_resource_schemas) =
+                if config.provider_only.unwrap_or(false) {
+                    (BTreeMap::new(), BTreeMap::new(), BTreeMap::new())
+                } else {
+                    (
+                        provider_schema.resource_schemas,
+                        provider_schema.data_source_schemas,
+                        provider_schema.ephemeral_resource_schemas,
+                    )
+                };
+
+            // Resources, datasources, and ephemeral resources share the provider module
+            let mut module_names = HashSet::new();
+
             // Resources
-            for (resource_name, resource) in &provider_schema.resource_schemas {
+            for (resource_name, resource) in resource_schemas {
                 let mut out = rustfile_template();
-                out.push(quote!(use super:: provider:: #provider_ident;));
                 let use_name_parts =
                     resource_name
                         .strip_prefix(&provider_prefix)
@@ -296,18 +608,47 @@ fn main() {
                         .split("_")
                         .map(ToString::to_string)
                         .collect::<Vec<String>>();
-                let nice_resource_name = to_snake(&use_name_parts);
+                let nice_resource_name = module_name(to_snake(&use_name_parts));
+                let (group, file_module_name) = module_location(layout, None, &use_name_parts);
+                let provider_module = if group.is_some() {
+                    quote!(super:: super:: provider)
+                } else {
+                    quote!(super:: provider)
+                };
+                out.push(quote!(use #provider_module:: #provider_ident;));
                 if whitelist && !include.remove(&nice_resource_name) {
                     continue;
                 }
                 if exclude.remove(&nice_resource_name) {
                     continue;
                 }
+                if !module_names.insert(nice_resource_name.clone()) {
+                    return Err(
+                        loga::err_with(
+                            "Multiple resources map to the same module name",
+                            ea!(resource = resource_name, module = nice_resource_name),
+                        ),
+                    );
+                }
                 println!("Generating {}", nice_resource_name);
                 let camel_name = to_camel(&use_name_parts);
                 let mut raw_fields = TopLevelFields::default();
-                generate_fields_from_value_map(&mut raw_fields, &use_name_parts, &resource.block.attributes, true);
-                generate_block_fields(&mut raw_fields, &use_name_parts, &resource.block.block_types, true);
+                generate_fields_from_value_map(
+                    &ctx,
+                    &nice_resource_name,
+                    &mut raw_fields,
+                    &use_name_parts,
+                    &resource.block.attributes,
+                    true,
+                ).context_with("Error generating resource", ea!(resource = resource_name))?;
+                generate_block_fields(
+                    &ctx,
+                    &nice_resource_name,
+                    &mut raw_fields,
+                    &use_name_parts,
+                    &resource.block.block_types,
+                    true,
+                ).context_with("Error generating resource", ea!(resource = resource_name))?;
                 raw_fields.finish(&camel_name);
                 let builder_fields = raw_fields.builder_fields;
                 let copy_builder_fields = raw_fields.copy_builder_fields;
@@ -326,7 +667,9 @@ fn main() {
                         #[serde(skip_serializing_if = "Option::is_none")] provider: Option < String >,
                         #[serde(skip_serializing_if = "SerdeSkipDefault::is_default")] lifecycle: ResourceLifecycle,
                         #[serde(skip_serializing_if = "Option::is_none")] for_each: Option < String >,
-                        #(#resource_fields,) *
+                        #[serde(skip_serializing_if = "Option::is_none")] count: Option < PrimField < i64 >>,
+                        #(#resource_fields,) * #[serde(flatten)] __terrars_raw: std:: collections:: BTreeMap < String,
+                        AnyField >,
                     }
                     struct #resource_inner_ident {
                         shared: StackShared,
@@ -338,6 +681,9 @@ fn main() {
                         fn shared(&self) -> &StackShared {
                             &self.0.shared
                         }
+                        fn attr_ref(&self, attr: &str) -> String {
+                            format!("{}.{}", self.extract_ref(), attr)
+                        }
                         pub fn depends_on(self, dep: &impl Referable) -> Self {
                             self.0.data.borrow_mut().depends_on.push(dep.extract_ref());
                             self
@@ -346,6 +692,45 @@ fn main() {
                             self.0.data.borrow_mut().provider = Some(provider.provider_ref());
                             self
                         }
+                        #[doc = "Create `v` instances of the resource (`count`). Use `index` to refer to an instance. Can't be combined with `for_each` (ex: creating the resource with `map`)."] pub fn set_count(
+                            self,
+                            v: impl Into<PrimField<i64>>
+                        ) -> Self {
+                            self.0.data.borrow_mut().count = Some(v.into());
+                            self
+                        }
+                        #[doc = "Only create the resource if `cond` is true (sets `count` to 1 or 0). Use `enabled_ref` to refer to the resource. Can't be combined with `for_each` (ex: creating the resource with `map`)."] pub fn set_enabled_if(
+                            self,
+                            cond: &impl Expr<bool>
+                        ) -> Self {
+                            let count = self.shared().add_sentinel(&format!("{} ? 1 : 0", cond.expr_raw().1));
+                            self.0.data.borrow_mut().count = Some(PrimField::Sentinel(count));
+                            self
+                        }
+                        #[doc = "Refer to the resource when it was made conditional with `set_enabled_if`. Attributes are `null` when the resource is disabled. Values nested in blocks aren't guarded, so only refer to them when the condition is true."] pub fn enabled_ref(
+                            &self
+                        ) -> #resource_ref_ident {
+                            #resource_ref_ident {
+                                shared: self.shared().clone(),
+                                base: self.extract_ref(),
+                                guarded: true,
+                            }
+                        }
+                        #[doc = "Refer to one instance of a resource created with `set_count`."] pub fn index(
+                            &self,
+                            i: usize
+                        ) -> #resource_ref_ident {
+                            #resource_ref_ident:: new(self.shared().clone(), format!("{}[{}]", self.extract_ref(), i))
+                        }
+                        #[doc = "Refer to one instance of a resource created with `for_each` (ex: with `map`) by its key. Use `extract_ref` on the result to get the address for `RunOptions::targets` or `RunOptions::replace`."] pub fn instance(
+                            &self,
+                            key: &str
+                        ) -> #resource_ref_ident {
+                            #resource_ref_ident:: new(
+                                self.shared().clone(),
+                                format!("{}[{}]", self.extract_ref(), key.to_string().to_expr_raw())
+                            )
+                        }
                         pub fn set_create_before_destroy(self, v: bool) -> Self {
                             self.0.data.borrow_mut().lifecycle.create_before_destroy = v;
                             self
@@ -387,6 +772,58 @@ fn main() {
                             self.0.data.borrow_mut().lifecycle.replace_triggered_by.push(attr.to_string());
                             self
                         }
+                        #[doc = "Remove all attributes set with `ignore_changes_to_attr` or `ignore_changes_to_all`."] pub fn clear_ignore_changes(
+                            self
+                        ) -> Self {
+                            self.0.data.borrow_mut().lifecycle.ignore_changes = None;
+                            self
+                        }
+                        #[doc = "Remove all references added with `replace_triggered_by_resource` or `replace_triggered_by_attr`."] pub fn clear_replace_triggered_by(
+                            self
+                        ) -> Self {
+                            self.0.data.borrow_mut().lifecycle.replace_triggered_by.clear();
+                            self
+                        }
+                        #[doc = "Reset all `lifecycle` settings to their defaults."] pub fn clear_lifecycle(self) -> Self {
+                            self.0.data.borrow_mut().lifecycle = Default::default();
+                            self
+                        }
+                        #[doc = "Merge a value that serializes to a json object into the `lifecycle` block (ex: arguments added in newer Terraform versions). This bypasses the typed lifecycle setters and takes precedence over them. Panics if the value doesn't serialize to an object."] pub fn set_lifecycle_raw(
+                            self,
+                            v: impl Serialize
+                        ) -> Self {
+                            match serde_json::to_value(v).unwrap() {
+                                serde_json::Value::Object(o) => self.0.data.borrow_mut().lifecycle.raw.extend(o),
+                                _ => panic!("set_lifecycle_raw value must serialize to a json object"),
+                            }
+                            self
+                        }
+                        #[doc = "Set attributes and blocks from a value that serializes to a json object in Terraform's json syntax (ex: the resource body from an existing `.tf.json` file). This bypasses type checking and takes precedence over values set with the typed setters. Strings are escaped like other literal strings, but may contain expressions converted to strings (ex: with `to_string`). Panics if the value doesn't serialize to an object."] pub fn set_from(
+                            self,
+                            v: impl Serialize
+                        ) -> Self {
+                            match serde_json::to_value(v).unwrap() {
+                                serde_json::Value::Object(o) => self.0.data.borrow_mut().__terrars_raw.extend(o.into_iter().map(|(k, v)| (k, AnyField::Literal(v)))),
+                                _ => panic!("set_from value must serialize to a json object"),
+                            }
+                            self
+                        }
+                        #[doc = "Set a single attribute or block in Terraform's json syntax, for last-minute overrides (ex: with a `serde_json::Value` or an expression). Like `set_from`, this bypasses type checking and takes precedence over values set with the typed setters."] pub fn set_data_raw(
+                            &self,
+                            k: impl ToString,
+                            v: impl Into<AnyField>
+                        ) {
+                            self.0.data.borrow_mut().__terrars_raw.insert(k.to_string(), v.into());
+                        }
+                        #[doc = "Get the current value of an attribute or block as it will be serialized, or `None` if it isn't set. Expressions appear as `_TERRARS_SENTINEL*` placeholders."] pub fn get_data_raw(
+                            &self,
+                            k: &str
+                        ) -> Option<serde_json::Value> {
+                            match serde_json::to_value(&self.0.data).unwrap() {
+                                serde_json::Value::Object(mut o) => o.remove(k),
+                                _ => None,
+                            }
+                        }
                         #(#resource_mut_methods) * #(#resource_ref_methods) *
                     }
                     impl Referable for #resource_ident {
@@ -396,6 +833,15 @@ fn main() {
                     }
                     impl Resource for #resource_ident {
                     }
+                    impl std:: fmt:: Debug for #resource_ident {
+                        fn fmt(&self, f: &mut std::fmt::Formatter <'_>) -> std::fmt::Result {
+                            f
+                                .debug_struct(stringify!(#resource_ident))
+                                .field("type", &#resource_name)
+                                .field("tf_id", &self.0.tf_id)
+                                .finish()
+                        }
+                    }
                     impl ToListMappable for #resource_ident {
                         type O = ListRef < #resource_ref_ident >;
                         fn do_map(self, base: String) -> Self::O {
@@ -419,8 +865,8 @@ fn main() {
                         #(#builder_fields,) *
                     }
                     impl #resource_builder_ident {
-                        pub fn build(self, stack:& mut Stack) -> #resource_ident {
-                            let out = #resource_ident(Rc:: new(#resource_inner_ident {
+                        fn create(self, stack:& Stack) -> #resource_ident {
+                            #resource_ident(Rc:: new(#resource_inner_ident {
                                 shared: stack.shared.clone(),
                                 tf_id: self.tf_id,
                                 data: RefCell:: new(#resource_inner_mut_ident {
@@ -428,106 +874,279 @@ fn main() {
                                     provider: None,
                                     lifecycle: core:: default:: Default:: default(),
                                     for_each: None,
-                                    #(#copy_builder_fields,) *
+                                    count: None,
+                                    #(#copy_builder_fields,) * __terrars_raw: Default:: default(),
                                 }),
-                            }));
+                            }))
+                        }
+                        pub fn build(self, stack:& mut Stack) -> #resource_ident {
+                            let out = self.create(stack);
                             stack.add_resource(out.0.clone());
                             out
                         }
+                        #[doc = "Like `build` but fails instead of adding the resource if `tf_id` isn't a valid Terraform identifier or is already used by another resource of the same type."] pub fn try_build(
+                            self,
+                            stack:& mut Stack
+                        ) -> Result < #resource_ident,
+                        StackError > {
+                            let out = self.create(stack);
+                            stack.try_add_resource(out.0.clone())?;
+                            Ok(out)
+                        }
                     }
                     pub struct #resource_ref_ident {
                         shared: StackShared,
-                        base: String
+                        base: String,
+                        guarded: bool
                     }
                     impl Ref for #resource_ref_ident {
                         fn new(shared: StackShared, base: String) -> Self {
                             Self {
                                 shared: shared,
                                 base: base,
+                                guarded: false,
                             }
                         }
                     }
                     impl #resource_ref_ident {
-                        fn extract_ref(&self) -> String {
-                            self.base.clone()
+                        #[doc = "Create a reference to an existing resource by its address (ex: `type.name`), for instance one managed outside this stack."] pub fn from_address(
+                            shared: StackShared,
+                            address:& str
+                        ) -> Self {
+                            Self::new(shared, address.to_string())
+                        }
+                        #[doc = "Refer to one instance of a resource created with `count`."] pub fn index(
+                            &self,
+                            i: usize
+                        ) -> Self {
+                            Self::new(self.shared.clone(), format!("{}[{}]", self.base, i))
+                        }
+                        #[doc = "Refer to one instance of a resource created with `for_each` by its key."] pub fn instance(
+                            &self,
+                            key: &str
+                        ) -> Self {
+                            Self::new(self.shared.clone(), format!("{}[{}]", self.base, key.to_string().to_expr_raw()))
                         }
                         fn shared(&self) -> &StackShared {
                             &self.shared
                         }
+                        fn attr_ref(&self, attr: &str) -> String {
+                            if self.guarded {
+                                format!("one({}[*].{})", self.base, attr)
+                            } else {
+                                format!("{}.{}", self.base, attr)
+                            }
+                        }
                         #(#resource_ref_methods) *
                     }
+                    impl Referable for #resource_ref_ident {
+                        fn extract_ref(&self) -> String {
+                            self.base.clone()
+                        }
+                    }
                     #(#extra_types) *
                 });
-                write_file(&provider_dir.join(format!("{}.rs", nice_resource_name)), out)?;
-                let path_ident = format_ident!("{}", nice_resource_name);
-                let feature_gate = if config.feature_gate.is_some() {
+                let filename = match &group {
+                    Some(group) => {
+                        create_dir_all(provider_dir.join(group))?;
+                        format!("{}/{}.rs", group, file_module_name)
+                    },
+                    None => format!("{}.rs", file_module_name),
+                };
+                if generated.contains(&filename) {
+                    return Err(
+                        loga::err_with(
+                            "Multiple resources map to the same module file",
+                            ea!(resource = resource_name, file = filename),
+                        ),
+                    );
+                }
+                write_file(
+                    &provider_dir.join(&filename),
+                    out,
+                ).context_with("Error writing resource", ea!(resource = resource_name))?;
+                generated.push(filename);
+                let path_ident = format_ident!("{}", file_module_name);
+                let (feature_gate, feature_doc) = if config.feature_gate.is_some() {
                     features.push(nice_resource_name.clone());
-                    quote!(#[cfg(feature = #nice_resource_name)])
+                    resource_features.push((to_snake(&use_name_parts), nice_resource_name.clone()));
+                    let doc = format!("Requires feature `{}`.", nice_resource_name);
+                    (quote!(#[cfg(feature = #nice_resource_name)]), quote!(#[doc = #doc]))
+                } else {
+                    (quote!(), quote!())
+                };
+                let inception_allow = if group.as_ref() == Some(&file_module_name) {
+                    quote!(#[allow(clippy::module_inception)])
                 } else {
                     quote!()
                 };
-                mod_out.push(quote!{
-                    #feature_gate pub mod #path_ident;
+                match group {
+                    Some(group) => &mut *group_mod_out.entry(group).or_default(),
+                    None => &mut mod_out,
+                }.push(quote!{
+                    #feature_gate #feature_doc #inception_allow pub mod #path_ident;
                     #feature_gate pub use #path_ident::*;
                 });
             }
 
-            // Data sources
-            for (datasource_name, datasource) in &provider_schema.data_source_schemas {
+            // Datasources and ephemeral resources
+            let read_blocks =
+                data_source_schemas
+                    .into_iter()
+                    .map(|(name, schema)| (&DATASOURCE_KIND, name, schema))
+                    .chain(
+                        ephemeral_resource_schemas
+                            .into_iter()
+                            .map(|(name, schema)| (&EPHEMERAL_KIND, name, schema)),
+                    );
+            for (kind, block_name, block) in read_blocks {
                 let mut out = rustfile_template();
-                out.push(quote!(use super:: provider:: #provider_ident;));
                 let use_name_parts =
-                    ["data"]
+                    [kind.prefix]
                         .into_iter()
                         .chain(
-                            datasource_name
+                            block_name
                                 .strip_prefix(&provider_prefix)
                                 .context_with(
                                     "Name missing expected provider prefix",
-                                    ea!(datasource = datasource_name, prefix = provider_prefix),
+                                    ea!(kind = kind.desc, name = block_name, prefix = provider_prefix),
                                 )?
                                 .split("_"),
                         )
                         .map(ToString::to_string)
                         .collect::<Vec<String>>();
-                let nice_datasource_name = to_snake(&use_name_parts);
-                if whitelist && !include.remove(&nice_datasource_name) {
+                let nice_block_name = module_name(to_snake(&use_name_parts));
+                let (group, file_module_name) = module_location(layout, Some(kind.prefix), &use_name_parts[1..]);
+                let provider_module = if group.is_some() {
+                    quote!(super:: super:: provider)
+                } else {
+                    quote!(super:: provider)
+                };
+                out.push(quote!(use #provider_module:: #provider_ident;));
+                if whitelist && !include.remove(&nice_block_name) {
+                    continue;
+                }
+                if exclude.remove(&nice_block_name) {
                     continue;
                 }
-                println!("Generating datasource {}", datasource_name);
+                if !module_names.insert(nice_block_name.clone()) {
+                    return Err(
+                        loga::err_with(
+                            "Multiple resources map to the same module name",
+                            ea!(kind = kind.desc, name = block_name, module = nice_block_name),
+                        ),
+                    );
+                }
+                println!("Generating {} {}", kind.desc, block_name);
                 let camel_name = to_camel(&use_name_parts);
                 let mut raw_fields = TopLevelFields::default();
-                generate_fields_from_value_map(&mut raw_fields, &use_name_parts, &datasource.block.attributes, true);
-                generate_block_fields(&mut raw_fields, &use_name_parts, &datasource.block.block_types, true);
+                generate_fields_from_value_map(
+                    &ctx,
+                    &nice_block_name,
+                    &mut raw_fields,
+                    &use_name_parts,
+                    &block.block.attributes,
+                    true,
+                ).context_with(format!("Error generating {}", kind.desc), ea!(name = block_name))?;
+                generate_block_fields(
+                    &ctx,
+                    &nice_block_name,
+                    &mut raw_fields,
+                    &use_name_parts,
+                    &block.block.block_types,
+                    true,
+                ).context_with(format!("Error generating {}", kind.desc), ea!(name = block_name))?;
                 raw_fields.finish(&camel_name);
                 let builder_fields = raw_fields.builder_fields;
                 let copy_builder_fields = raw_fields.copy_builder_fields;
                 let extra_types = raw_fields.extra_types;
-                let datasource_fields = raw_fields.fields;
-                let datasource_mut_methods = raw_fields.mut_methods;
-                let datasource_ref_methods = raw_fields.ref_methods;
-                let datasource_ident = format_ident!("{}", camel_name);
-                let datasource_inner_ident = format_ident!("{}_", camel_name);
-                let datasource_inner_mut_ident = format_ident!("{}Data", camel_name);
-                let datasource_builder_ident = format_ident!("Build{}", camel_name);
-                let datasource_ref_ident = format_ident!("{}Ref", camel_name);
+                let block_fields = raw_fields.fields;
+                let block_mut_methods = raw_fields.mut_methods;
+                let block_ref_methods = raw_fields.ref_methods;
+                let block_ident = format_ident!("{}", camel_name);
+                let block_inner_ident = format_ident!("{}_", camel_name);
+                let block_inner_mut_ident = format_ident!("{}Data", camel_name);
+                let block_builder_ident = format_ident!("Build{}", camel_name);
+                let block_ref_ident = format_ident!("{}Ref", camel_name);
+                let block_trait = format_ident!("{}", kind.trait_name);
+                let block_inner_trait = format_ident!("{}_", kind.trait_name);
+                let extract_type = format_ident!("extract_{}_type", kind.trait_name.to_lowercase());
+                let add_method = format_ident!("add_{}", kind.trait_name.to_lowercase());
+                let try_add_method = format_ident!("try_add_{}", kind.trait_name.to_lowercase());
+                let ref_format = format!("{}.{{}}.{{}}", kind.prefix);
+                let desc = kind.desc;
+                let (verb, past) = kind.verb;
+                let cap_verb = format!("{}{}", verb[..1].to_uppercase(), &verb[1..]);
+                let a_desc = format!("{} {}", kind.article, desc);
+                let set_count_doc =
+                    format!(
+                        "{} `v` instances of the {} (`count`). Use `index` to refer to an instance. Can't be combined with `for_each` (ex: creating the {} with `map`).",
+                        cap_verb,
+                        desc,
+                        desc
+                    );
+                let set_enabled_if_doc =
+                    format!(
+                        "Only {} the {} if `cond` is true (sets `count` to 1 or 0). Use `enabled_ref` to refer to the {}. Can't be combined with `for_each` (ex: creating the {} with `map`).",
+                        verb,
+                        desc,
+                        desc,
+                        desc
+                    );
+                let enabled_ref_doc =
+                    format!(
+                        "Refer to the {} when it was made conditional with `set_enabled_if`. Attributes are `null` when the {} is disabled. Values nested in blocks aren't guarded, so only refer to them when the condition is true.",
+                        desc,
+                        desc
+                    );
+                let index_doc = format!("Refer to one instance of {} {} with `set_count`.", a_desc, past);
+                let instance_doc =
+                    format!(
+                        "Refer to one instance of {} {} with `for_each` (ex: with `map`) by its key. Use `extract_ref` on the result to get the address for `RunOptions::targets` or `RunOptions::replace`.",
+                        a_desc,
+                        past
+                    );
+                let set_from_doc =
+                    format!(
+                        "Set attributes and blocks from a value that serializes to a json object in Terraform's json syntax (ex: the {} body from an existing `.tf.json` file). This bypasses type checking and takes precedence over values set with the typed setters. Strings are escaped like other literal strings, but may contain expressions converted to strings (ex: with `to_string`). Panics if the value doesn't serialize to an object.",
+                        desc
+                    );
+                let try_build_doc =
+                    format!(
+                        "Like `build` but fails instead of adding the {} if `tf_id` isn't a valid Terraform identifier or is already used by another {} of the same type.",
+                        desc,
+                        desc
+                    );
+                let from_address_doc =
+                    format!(
+                        "Create a reference to an existing {} by its address (ex: `{}.type.name`), for instance one defined outside this stack.",
+                        desc,
+                        kind.prefix
+                    );
+                let ref_index_doc = format!("Refer to one instance of {} {} with `count`.", a_desc, past);
+                let ref_instance_doc = format!("Refer to one instance of {} {} with `for_each` by its key.", a_desc, past);
                 out.push(quote!{
-                    #[derive(Serialize)] struct #datasource_inner_mut_ident {
+                    #[derive(Serialize)] struct #block_inner_mut_ident {
                         #[serde(skip_serializing_if = "Vec::is_empty")] depends_on: Vec < String >,
                         #[serde(skip_serializing_if = "SerdeSkipDefault::is_default")] provider: Option < String >,
                         #[serde(skip_serializing_if = "Option::is_none")] for_each: Option < String >,
-                        #(#datasource_fields,) *
+                        #[serde(skip_serializing_if = "Option::is_none")] count: Option < PrimField < i64 >>,
+                        #(#block_fields,) * #[serde(flatten)] __terrars_raw: std:: collections:: BTreeMap < String,
+                        AnyField >,
                     }
-                    struct #datasource_inner_ident {
+                    struct #block_inner_ident {
                         shared: StackShared,
                         tf_id: String,
-                        data: RefCell < #datasource_inner_mut_ident >,
+                        data: RefCell < #block_inner_mut_ident >,
                     }
-                    #[derive(Clone)] pub struct #datasource_ident(Rc < #datasource_inner_ident >);
-                    impl #datasource_ident {
+                    #[derive(Clone)] pub struct #block_ident(Rc < #block_inner_ident >);
+                    impl #block_ident {
                         fn shared(&self) -> &StackShared {
                             &self.0.shared
                         }
+                        fn attr_ref(&self, attr: &str) -> String {
+                            format!("{}.{}", self.extract_ref(), attr)
+                        }
                         pub fn depends_on(self, dep: &impl Referable) -> Self {
                             self.0.data.borrow_mut().depends_on.push(dep.extract_ref());
                             self
@@ -536,25 +1155,83 @@ fn main() {
                             self.0.data.borrow_mut().provider = Some(provider.provider_ref());
                             self
                         }
-                        #(#datasource_mut_methods) * #(#datasource_ref_methods) *
+                        #[doc = #set_count_doc] pub fn set_count(
+                            self,
+                            v: impl Into<PrimField<i64>>
+                        ) -> Self {
+                            self.0.data.borrow_mut().count = Some(v.into());
+                            self
+                        }
+                        #[doc = #set_enabled_if_doc] pub fn set_enabled_if(
+                            self,
+                            cond: &impl Expr<bool>
+                        ) -> Self {
+                            let count = self.shared().add_sentinel(&format!("{} ? 1 : 0", cond.expr_raw().1));
+                            self.0.data.borrow_mut().count = Some(PrimField::Sentinel(count));
+                            self
+                        }
+                        #[doc = #enabled_ref_doc] pub fn enabled_ref(
+                            &self
+                        ) -> #block_ref_ident {
+                            #block_ref_ident {
+                                shared: self.shared().clone(),
+                                base: self.extract_ref(),
+                                guarded: true,
+                            }
+                        }
+                        #[doc = #index_doc] pub fn index(
+                            &self,
+                            i: usize
+                        ) -> #block_ref_ident {
+                            #block_ref_ident:: new(self.shared().clone(), format!("{}[{}]", self.extract_ref(), i))
+                        }
+                        #[doc = #instance_doc] pub fn instance(
+                            &self,
+                            key: &str
+                        ) -> #block_ref_ident {
+                            #block_ref_ident:: new(
+                                self.shared().clone(),
+                                format!("{}[{}]", self.extract_ref(), key.to_string().to_expr_raw())
+                            )
+                        }
+                        #[doc = #set_from_doc] pub fn set_from(
+                            self,
+                            v: impl Serialize
+                        ) -> Self {
+                            match serde_json::to_value(v).unwrap() {
+                                serde_json::Value::Object(o) => self.0.data.borrow_mut().__terrars_raw.extend(o.into_iter().map(|(k, v)| (k, AnyField::Literal(v)))),
+                                _ => panic!("set_from value must serialize to a json object"),
+                            }
+                            self
+                        }
+                        #(#block_mut_methods) * #(#block_ref_methods) *
                     }
-                    impl Referable for #datasource_ident {
+                    impl Referable for #block_ident {
                         fn extract_ref(&self) -> String {
-                            format!("data.{}.{}", self.0.extract_datasource_type(), self.0.extract_tf_id())
+                            format!(#ref_format, self.0.#extract_type(), self.0.extract_tf_id())
                         }
                     }
-                    impl Datasource for #datasource_ident {
+                    impl #block_trait for #block_ident {
                     }
-                    impl ToListMappable for #datasource_ident {
-                        type O = ListRef < #datasource_ref_ident >;
+                    impl std:: fmt:: Debug for #block_ident {
+                        fn fmt(&self, f: &mut std::fmt::Formatter <'_>) -> std::fmt::Result {
+                            f
+                                .debug_struct(stringify!(#block_ident))
+                                .field("type", &#block_name)
+                                .field("tf_id", &self.0.tf_id)
+                                .finish()
+                        }
+                    }
+                    impl ToListMappable for #block_ident {
+                        type O = ListRef < #block_ref_ident >;
                         fn do_map(self, base: String) -> Self::O {
                             self.0.data.borrow_mut().for_each = Some(format!("${{{}}}", base));
                             ListRef::new(self.0.shared.clone(), self.extract_ref())
                         }
                     }
-                    impl Datasource_ for #datasource_inner_ident {
-                        fn extract_datasource_type(&self) -> String {
-                            #datasource_name.into()
+                    impl #block_inner_trait for #block_inner_ident {
+                        fn #extract_type(&self) -> String {
+                            #block_name.into()
                         }
                         fn extract_tf_id(&self) -> String {
                             self.tf_id.clone()
@@ -563,89 +1240,240 @@ fn main() {
                             serde_json::to_value(&self.data).unwrap()
                         }
                     }
-                    pub struct #datasource_builder_ident {
+                    pub struct #block_builder_ident {
                         pub tf_id: String,
                         #(#builder_fields,) *
                     }
-                    impl #datasource_builder_ident {
-                        pub fn build(self, stack:& mut Stack) -> #datasource_ident {
-                            let out = #datasource_ident(Rc:: new(#datasource_inner_ident {
+                    impl #block_builder_ident {
+                        fn create(self, stack:& Stack) -> #block_ident {
+                            #block_ident(Rc:: new(#block_inner_ident {
                                 shared: stack.shared.clone(),
                                 tf_id: self.tf_id,
-                                data: RefCell:: new(#datasource_inner_mut_ident {
+                                data: RefCell:: new(#block_inner_mut_ident {
                                     depends_on: core:: default:: Default:: default(),
                                     provider: None,
                                     for_each: None,
-                                    #(#copy_builder_fields,) *
+                                    count: None,
+                                    #(#copy_builder_fields,) * __terrars_raw: Default:: default(),
                                 }),
-                            }));
-                            stack.add_datasource(out.0.clone());
+                            }))
+                        }
+                        pub fn build(self, stack:& mut Stack) -> #block_ident {
+                            let out = self.create(stack);
+                            stack.#add_method(out.0.clone());
                             out
                         }
+                        #[doc = #try_build_doc] pub fn try_build(
+                            self,
+                            stack:& mut Stack
+                        ) -> Result < #block_ident,
+                        StackError > {
+                            let out = self.create(stack);
+                            stack.#try_add_method(out.0.clone())?;
+                            Ok(out)
+                        }
                     }
-                    pub struct #datasource_ref_ident {
+                    pub struct #block_ref_ident {
                         shared: StackShared,
-                        base: String
+                        base: String,
+                        guarded: bool
                     }
-                    impl Ref for #datasource_ref_ident {
+                    impl Ref for #block_ref_ident {
                         fn new(shared: StackShared, base: String) -> Self {
                             Self {
                                 shared: shared,
                                 base: base,
+                                guarded: false,
                             }
                         }
                     }
-                    impl #datasource_ref_ident {
+                    impl #block_ref_ident {
+                        #[doc = #from_address_doc] pub fn from_address(
+                            shared: StackShared,
+                            address:& str
+                        ) -> Self {
+                            Self::new(shared, address.to_string())
+                        }
+                        #[doc = #ref_index_doc] pub fn index(
+                            &self,
+                            i: usize
+                        ) -> Self {
+                            Self::new(self.shared.clone(), format!("{}[{}]", self.base, i))
+                        }
+                        #[doc = #ref_instance_doc] pub fn instance(
+                            &self,
+                            key: &str
+                        ) -> Self {
+                            Self::new(self.shared.clone(), format!("{}[{}]", self.base, key.to_string().to_expr_raw()))
+                        }
                         fn shared(&self) -> &StackShared {
                             &self.shared
                         }
+                        fn attr_ref(&self, attr: &str) -> String {
+                            if self.guarded {
+                                format!("one({}[*].{})", self.base, attr)
+                            } else {
+                                format!("{}.{}", self.base, attr)
+                            }
+                        }
+                        #(#block_ref_methods) *
+                    }
+                    impl Referable for #block_ref_ident {
                         fn extract_ref(&self) -> String {
                             self.base.clone()
                         }
-                        #(#datasource_ref_methods) *
                     }
                     #(#extra_types) *
                 });
-                write_file(&provider_dir.join(format!("{}.rs", nice_datasource_name)), out)?;
-                let path_ident = format_ident!("{}", nice_datasource_name);
-                let feature_gate = if config.feature_gate.is_some() {
-                    features.push(nice_datasource_name.clone());
-                    quote!(#[cfg(feature = #nice_datasource_name)])
+                let filename = match &group {
+                    Some(group) => {
+                        create_dir_all(provider_dir.join(group))?;
+                        format!("{}/{}.rs", group, file_module_name)
+                    },
+                    None => format!("{}.rs", file_module_name),
+                };
+                if generated.contains(&filename) {
+                    return Err(
+                        loga::err_with(
+                            "Multiple resources map to the same module file",
+                            ea!(kind = kind.desc, name = block_name, file = filename),
+                        ),
+                    );
+                }
+                write_file(
+                    &provider_dir.join(&filename),
+                    out,
+                ).context_with(format!("Error writing {}", kind.desc), ea!(name = block_name))?;
+                generated.push(filename);
+                let path_ident = format_ident!("{}", file_module_name);
+                let (feature_gate, feature_doc) = if config.feature_gate.is_some() {
+                    features.push(nice_block_name.clone());
+                    let doc = format!("Requires feature `{}`.", nice_block_name);
+                    (quote!(#[cfg(feature = #nice_block_name)]), quote!(#[doc = #doc]))
+                } else {
+                    (quote!(), quote!())
+                };
+                let inception_allow = if group.as_ref() == Some(&file_module_name) {
+                    quote!(#[allow(clippy::module_inception)])
                 } else {
                     quote!()
                 };
-                mod_out.push(quote!{
-                    #feature_gate pub mod #path_ident;
+                match group {
+                    Some(group) => &mut *group_mod_out.entry(group).or_default(),
+                    None => &mut mod_out,
+                }.push(quote!{
+                    #feature_gate #feature_doc #inception_allow pub mod #path_ident;
                     #feature_gate pub use #path_ident::*;
                 });
             }
+            for (group, group_mod) in group_mod_out {
+                let filename = format!("{}/mod.rs", group);
+                write_file(&provider_dir.join(&filename), group_mod)?;
+                generated.push(filename);
+                let path_ident = format_ident!("{}", group);
+                mod_out.push(quote!(pub mod #path_ident;));
+            }
             write_file(&provider_dir.join("mod.rs"), mod_out)?;
+            generated.push("mod.rs".to_string());
+            if config.lockfile.unwrap_or(false) {
+                fs::copy(
+                    dir.path().join(LOCKFILE_NAME),
+                    provider_dir.join(LOCKFILE_NAME),
+                ).context("Error copying lockfile from schema extraction")?;
+                generated.push(LOCKFILE_NAME.to_string());
+            }
+            fs::write(
+                &manifest_path,
+                generated.join("\n"),
+            ).context_with("Error writing manifest of generated files", ea!(path = manifest_path.to_string_lossy()))?;
             if whitelist && !include.is_empty() {
                 return Err(
                     loga::err_with("Included resources/datasources were not found", ea!(included = include.dbg_str())),
                 );
             }
-            if features.len() > 0 {
-                let cargo_path = config.feature_gate.unwrap();
+            // Features enabling both a resource and the datasource with the same name
+            let mut combined_features = BTreeMap::new();
+            for (base_name, resource_feature) in resource_features {
+                let datasource_feature = format!("data_{}", base_name);
+                let combined_feature = format!("{}_and_data", base_name);
+                if features.contains(&datasource_feature) && !features.contains(&combined_feature) {
+                    combined_features.insert(combined_feature, vec![resource_feature, datasource_feature]);
+                }
+            }
+            features.extend(combined_features.keys().cloned());
+            if let Some(cargo_path) = &config.feature_gate {
+                // Only touch features generated by a previous run, leaving any user-defined
+                // features alone
+                let features_path = provider_dir.join(FEATURES_MANIFEST_NAME);
+                let old_features = if features_path.exists() {
+                    fs::read_to_string(
+                        &features_path,
+                    )
+                        .context_with(
+                            "Error reading manifest of previously generated features",
+                            ea!(path = features_path.to_string_lossy()),
+                        )?
+                        .lines()
+                        .filter(|f| !f.is_empty())
+                        .map(ToString::to_string)
+                        .collect::<Vec<String>>()
+                } else {
+                    vec![]
+                };
                 let mut manifest =
                     cargo_toml::Manifest::from_slice(
                         &fs::read(
-                            &cargo_path,
+                            cargo_path,
                         ).context_with(
                             "Error opening Cargo.toml to update features",
                             ea!(path = cargo_path.to_string_lossy()),
                         )?,
                     ).context_with("Error parsing Cargo.toml", ea!(path = cargo_path.to_string_lossy()))?;
-                manifest.features.clear();
-                for f in features {
-                    manifest.features.insert(f, vec![]);
+                for f in old_features {
+                    if !features.contains(&f) {
+                        manifest.features.remove(&f);
+                    }
+                }
+                if args.check.is_some() {
+                    if features.iter().any(|f| !manifest.features.contains_key(f)) ||
+                        combined_features.iter().any(|(f, deps)| manifest.features.get(f) != Some(deps)) {
+                        out_of_date.push(cargo_path.clone());
+                    }
+                } else {
+                    for f in &features {
+                        manifest.features.entry(f.clone()).or_insert_with(Vec::new);
+                    }
+                    for (f, deps) in &combined_features {
+                        manifest.features.insert(f.clone(), deps.clone());
+                    }
+                    fs::write(
+                        cargo_path,
+                        toml::to_string(&manifest).context("Error serializing modified Cargo.toml")?.into_bytes(),
+                    ).context_with("Error writing to Cargo.toml", ea!(path = cargo_path.to_string_lossy()))?;
                 }
                 fs::write(
-                    &cargo_path,
-                    &toml::to_string(&manifest).context("Error serializing modified Cargo.toml")?.into_bytes(),
-                ).context_with("Error writing to Cargo.toml", ea!(path = cargo_path.to_string_lossy()))?;
+                    &features_path,
+                    features.join("\n"),
+                ).context_with(
+                    "Error writing manifest of generated features",
+                    ea!(path = features_path.to_string_lossy()),
+                )?;
+            }
+            if args.check.is_some() {
+                out_of_date.extend(compare_generated(&provider_dir, &config.dest)?);
             }
         }
+        if !out_of_date.is_empty() {
+            for path in &out_of_date {
+                println!("{}", path.to_string_lossy());
+            }
+            return Err(
+                loga::err_with(
+                    "Generated code is out of date",
+                    ea!(paths = out_of_date.iter().map(|p| p.to_string_lossy()).collect::<Vec<_>>().join(", ")),
+                ),
+            );
+        }
         Ok(())
     }) {
         Ok(_) => { },
@@ -654,3 +1482,34 @@ fn main() {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use super::{
+        read_manifest,
+        MANIFEST_NAME,
+    };
+
+    #[test]
+    fn manifest_relative_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(MANIFEST_NAME), "provider.rs\n\ncompute/instance.rs\nmod.rs").unwrap();
+        assert_eq!(read_manifest(dir.path()).unwrap(), vec!["provider.rs", "compute/instance.rs", "mod.rs"]);
+    }
+
+    #[test]
+    fn manifest_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_manifest(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn manifest_rejects_escaping_entries() {
+        for entry in ["../lib.rs", "compute/../../lib.rs", "/etc/passwd", "./mod.rs"] {
+            let dir = tempfile::tempdir().unwrap();
+            fs::write(dir.path().join(MANIFEST_NAME), format!("mod.rs\n{}", entry)).unwrap();
+            assert!(read_manifest(dir.path()).is_err(), "{}", entry);
+        }
+    }
+}
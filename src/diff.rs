@@ -0,0 +1,310 @@
+use std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    fmt::Display,
+};
+use serde::{
+    Serialize,
+};
+use serde_json::{
+    Value,
+};
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A changed attribute. `path` is dotted, with list indexes in brackets (ex:
+/// `ingress[0].cidr_blocks`). `old` is missing if the attribute was added and
+/// `new` is missing if it was removed.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct AttributeDiff {
+    pub path: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// A changed resource, datasource, provider, etc. `attributes` is only filled for
+/// `ChangeKind::Changed`.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ComponentDiff {
+    /// The Terraform address, ex: `aws_instance.web`, `data.aws_ami.ubuntu`,
+    /// `var.region`
+    pub address: String,
+    pub kind: ChangeKind,
+    pub attributes: Vec<AttributeDiff>,
+}
+
+/// Differences between two serialized stacks, from `diff_configs`. Serialize it
+/// for a machine-readable form or use `Display` for a human-readable summary.
+#[derive(Serialize, Clone, Debug, PartialEq, Default)]
+pub struct ConfigDiff {
+    pub components: Vec<ComponentDiff>,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+impl Display for ConfigDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in &self.components {
+            let sigil = match c.kind {
+                ChangeKind::Added => "+",
+                ChangeKind::Removed => "-",
+                ChangeKind::Changed => "~",
+            };
+            writeln!(f, "{} {}", sigil, c.address)?;
+            for a in &c.attributes {
+                let show = |v: &Option<Value>| match v {
+                    Some(v) => v.to_string(),
+                    None => "(none)".to_string(),
+                };
+                writeln!(f, "    {}: {} -> {}", a.path, show(&a.old), show(&a.new))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare two stacks serialized by `Stack::serialize` (parsed as json) and list
+/// the added, removed, and changed components and attributes. This only compares
+/// the configuration, so it can't tell what Terraform will actually change (use
+/// `plan` for that).
+pub fn diff_configs(old: &Value, new: &Value) -> ConfigDiff {
+    let old = components(old);
+    let new = components(new);
+    let mut out = ConfigDiff::default();
+    for address in old.keys().chain(new.keys()).collect::<BTreeSet<_>>() {
+        match (old.get(address), new.get(address)) {
+            (Some(_), None) => out.components.push(ComponentDiff {
+                address: address.clone(),
+                kind: ChangeKind::Removed,
+                attributes: vec![],
+            }),
+            (None, Some(_)) => out.components.push(ComponentDiff {
+                address: address.clone(),
+                kind: ChangeKind::Added,
+                attributes: vec![],
+            }),
+            (Some(o), Some(n)) => {
+                let mut attributes = vec![];
+                diff_values(&mut attributes, "", o, n);
+                if !attributes.is_empty() {
+                    out.components.push(ComponentDiff {
+                        address: address.clone(),
+                        kind: ChangeKind::Changed,
+                        attributes: attributes,
+                    });
+                }
+            },
+            (None, None) => unreachable!(),
+        }
+    }
+    out
+}
+
+/// Split a serialized stack into components keyed by address.
+fn components(v: &Value) -> BTreeMap<String, &Value> {
+    let mut out = BTreeMap::new();
+    let top = match v.as_object() {
+        Some(t) => t,
+        None => return out,
+    };
+    for (k, v) in top {
+        match (k.as_str(), v.as_object()) {
+            ("resource", Some(types)) | ("data", Some(types)) | ("ephemeral", Some(types)) => {
+                let prefix = match k.as_str() {
+                    "resource" => "".to_string(),
+                    k => format!("{}.", k),
+                };
+                for (type_, instances) in types {
+                    if let Some(instances) = instances.as_object() {
+                        for (name, body) in instances {
+                            out.insert(format!("{}{}.{}", prefix, type_, name), body);
+                        }
+                    }
+                }
+            },
+            ("variable", Some(items)) |
+            ("output", Some(items)) |
+            ("provider", Some(items)) |
+            ("module", Some(items)) => {
+                let prefix = match k.as_str() {
+                    "variable" => "var",
+                    k => k,
+                };
+                for (name, body) in items {
+                    out.insert(format!("{}.{}", prefix, name), body);
+                }
+            },
+            _ => {
+                out.insert(k.clone(), v);
+            },
+        }
+    }
+    out
+}
+
+fn join_path(path: &str, k: &str) -> String {
+    if path.is_empty() {
+        k.to_string()
+    } else {
+        format!("{}.{}", path, k)
+    }
+}
+
+fn diff_values(out: &mut Vec<AttributeDiff>, path: &str, old: &Value, new: &Value) {
+    match (old, new) {
+        (Value::Object(o), Value::Object(n)) => {
+            for k in o.keys().chain(n.keys()).collect::<BTreeSet<_>>() {
+                let path = join_path(path, k);
+                match (o.get(k), n.get(k)) {
+                    (Some(o), Some(n)) => diff_values(out, &path, o, n),
+                    (o, n) => out.push(AttributeDiff {
+                        path: path,
+                        old: o.cloned(),
+                        new: n.cloned(),
+                    }),
+                }
+            }
+        },
+        (Value::Array(o), Value::Array(n)) if o.len() == n.len() => {
+            for (i, (o, n)) in o.iter().zip(n).enumerate() {
+                diff_values(out, &format!("{}[{}]", path, i), o, n);
+            }
+        },
+        (o, n) => {
+            if o != n {
+                out.push(AttributeDiff {
+                    path: path.to_string(),
+                    old: Some(o.clone()),
+                    new: Some(n.clone()),
+                });
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::{
+        diff_configs,
+        AttributeDiff,
+        ChangeKind,
+        ComponentDiff,
+    };
+
+    #[test]
+    fn components() {
+        let old = json!({
+            "resource": {
+                "aws_instance": {
+                    "web": { "ami": "a" },
+                    "old": { "ami": "a" },
+                },
+            },
+            "data": { "aws_ami": { "ubuntu": { "owners": ["x"] } } },
+            "variable": { "region": { "type": "string" } },
+        });
+        let new = json!({
+            "resource": {
+                "aws_instance": {
+                    "web": { "ami": "b" },
+                    "new": { "ami": "a" },
+                },
+            },
+            "data": { "aws_ami": { "ubuntu": { "owners": ["x"] } } },
+            "output": { "ip": { "value": "${aws_instance.web.ip}" } },
+            "variable": { "region": { "type": "string" } },
+        });
+        let diff = diff_configs(&old, &new);
+        assert_eq!(diff.components, vec![ComponentDiff {
+            address: "aws_instance.new".into(),
+            kind: ChangeKind::Added,
+            attributes: vec![],
+        }, ComponentDiff {
+            address: "aws_instance.old".into(),
+            kind: ChangeKind::Removed,
+            attributes: vec![],
+        }, ComponentDiff {
+            address: "aws_instance.web".into(),
+            kind: ChangeKind::Changed,
+            attributes: vec![AttributeDiff {
+                path: "ami".into(),
+                old: Some(json!("a")),
+                new: Some(json!("b")),
+            }],
+        }, ComponentDiff {
+            address: "output.ip".into(),
+            kind: ChangeKind::Added,
+            attributes: vec![],
+        }]);
+        assert!(diff_configs(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn nested_attributes() {
+        let old = json!({ "resource": { "aws_security_group": { "sg": {
+            "tags": { "a": "1", "b": "2" },
+            "ingress": [{ "from_port": 80, "cidr_blocks": ["0.0.0.0/0"] }],
+        } } } });
+        let new = json!({ "resource": { "aws_security_group": { "sg": {
+            "tags": { "a": "1", "c": "3" },
+            "ingress": [{ "from_port": 443, "cidr_blocks": ["10.0.0.0/8"] }],
+        } } } });
+        assert_eq!(diff_configs(&old, &new).components[0].attributes, vec![AttributeDiff {
+            path: "ingress[0].cidr_blocks[0]".into(),
+            old: Some(json!("0.0.0.0/0")),
+            new: Some(json!("10.0.0.0/8")),
+        }, AttributeDiff {
+            path: "ingress[0].from_port".into(),
+            old: Some(json!(80)),
+            new: Some(json!(443)),
+        }, AttributeDiff {
+            path: "tags.b".into(),
+            old: Some(json!("2")),
+            new: None,
+        }, AttributeDiff {
+            path: "tags.c".into(),
+            old: None,
+            new: Some(json!("3")),
+        }]);
+    }
+
+    #[test]
+    fn array_length_change() {
+        // Elements can't be matched up when the length changes, so the whole array is
+        // reported
+        let old = json!({ "resource": { "t": { "x": { "list": [1, 2] } } } });
+        let new = json!({ "resource": { "t": { "x": { "list": [1, 2, 3] } } } });
+        assert_eq!(diff_configs(&old, &new).components[0].attributes, vec![AttributeDiff {
+            path: "list".into(),
+            old: Some(json!([1, 2])),
+            new: Some(json!([1, 2, 3])),
+        }]);
+    }
+
+    #[test]
+    fn display() {
+        let old = json!({
+            "resource": { "t": { "a": { "x": 1, "y": "z" }, "b": {} } },
+        });
+        let new = json!({
+            "resource": { "t": { "a": { "x": 2 }, "c": {} } },
+        });
+        assert_eq!(
+            diff_configs(&old, &new).to_string(),
+            "~ t.a\n    x: 1 -> 2\n    y: \"z\" -> (none)\n- t.b\n+ t.c\n"
+        );
+    }
+}
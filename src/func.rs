@@ -3,7 +3,11 @@ use crate::{
         Expr,
     },
     prim_ref::PrimExpr,
+    any_ref::AnyRef,
+    list_ref::ListRef,
+    rec_ref::RecRef,
     PrimType,
+    Ref,
     PrimField,
     StackShared,
 };
@@ -32,20 +36,34 @@ pub struct Func {
 
 impl Func {
     /// Add an argument to the function call
-    pub fn a<T: PrimType>(mut self, s: impl ToFuncArg<T>) -> Self {
+    pub fn a<T: PrimType>(self, s: impl ToFuncArg<T>) -> Self {
+        let (_, s) = s.to_func_arg(&self.shared).expr_raw();
+        self.push_arg(&s)
+    }
+
+    /// Add an argument of any type (ex: an object) to the function call
+    pub fn a_any(self, s: &AnyRef) -> Self {
+        self.push_arg(&s.base)
+    }
+
+    /// Add a list argument to the function call
+    pub fn a_list<T: Ref>(self, s: &ListRef<T>) -> Self {
+        self.push_arg(&s.base)
+    }
+
+    fn push_arg(mut self, raw: &str) -> Self {
         if !self.first {
             self.data.push_str(", ");
         } else {
             self.first = false;
         }
-        let (_, s) = s.to_func_arg(&self.shared).expr_raw();
-        self.data.push_str(&s);
+        self.data.push_str(raw);
         self
     }
 
     /// Return an expression representing indexing the result of the function call
     pub fn index<T: PrimType>(&self, i: usize) -> PrimExpr<T> {
-        PrimExpr(self.shared.clone(), format!("{})[{}]", self.data, i), std::marker::PhantomData::default())
+        PrimExpr(self.shared.clone(), format!("{})[{}]", self.data, i), std::marker::PhantomData)
     }
 }
 
@@ -64,6 +82,24 @@ impl<T: PrimType> Into<PrimField<T>> for Func {
 impl<T: PrimType> Into<PrimExpr<T>> for Func {
     fn into(self) -> PrimExpr<T> {
         let (s, raw) = <Func as Expr<T>>::expr_raw(&self);
-        return PrimExpr(s.clone(), raw, std::marker::PhantomData::default());
+        return PrimExpr(s.clone(), raw, std::marker::PhantomData);
+    }
+}
+
+impl Into<AnyRef> for Func {
+    fn into(self) -> AnyRef {
+        AnyRef::new(self.shared, format!("{})", self.data))
+    }
+}
+
+impl<T: Ref> Into<ListRef<T>> for Func {
+    fn into(self) -> ListRef<T> {
+        ListRef::new(self.shared, format!("{})", self.data))
+    }
+}
+
+impl<T: Ref> Into<RecRef<T>> for Func {
+    fn into(self) -> RecRef<T> {
+        RecRef::new(self.shared, format!("{})", self.data))
     }
 }
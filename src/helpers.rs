@@ -1,7 +1,15 @@
+use serde::Serialize;
+use serde_json::Value;
 use crate::{
+    escape_template_literal,
+    AnyRef,
+    ListRef,
+    RecRef,
+    Ref,
     Stack,
     ToFuncArg,
     PrimExpr,
+    PrimType,
 };
 
 /// Generates a call to Terraform method `base64encode`.
@@ -14,6 +22,30 @@ pub fn tf_base64decode(stack: &Stack, e: impl ToFuncArg<String>) -> PrimExpr<Str
     return stack.func("base64decode").a(e).into();
 }
 
+/// Generates a call to Terraform method `base64gzip`, which gzips a string and
+/// base64-encodes the result.
+pub fn tf_base64gzip(stack: &Stack, e: impl ToFuncArg<String>) -> PrimExpr<String> {
+    return stack.func("base64gzip").a(e).into();
+}
+
+/// Generates a call to Terraform method `filemd5`. Relative paths are resolved from
+/// the directory Terraform is run in (the run directory for `Stack::run`).
+pub fn tf_filemd5(stack: &Stack, path: impl ToFuncArg<String>) -> PrimExpr<String> {
+    return stack.func("filemd5").a(path).into();
+}
+
+/// Generates a call to Terraform method `filesha256`. Relative paths are resolved
+/// like `tf_filemd5`.
+pub fn tf_filesha256(stack: &Stack, path: impl ToFuncArg<String>) -> PrimExpr<String> {
+    return stack.func("filesha256").a(path).into();
+}
+
+/// Generates a call to Terraform method `filesha512`. Relative paths are resolved
+/// like `tf_filemd5`.
+pub fn tf_filesha512(stack: &Stack, path: impl ToFuncArg<String>) -> PrimExpr<String> {
+    return stack.func("filesha512").a(path).into();
+}
+
 /// Generates a call to Terraform method `substr`.
 pub fn tf_substr(
     stack: &Stack,
@@ -42,6 +74,239 @@ pub fn tf_trim_prefix(
     return stack.func("trimprefix").a(original).a(prefix).into();
 }
 
+/// Generates a call to Terraform method `startswith`, which is true if `s` starts
+/// with `prefix`.
+pub fn tf_startswith(stack: &Stack, s: impl ToFuncArg<String>, prefix: impl ToFuncArg<String>) -> PrimExpr<bool> {
+    return stack.func("startswith").a(s).a(prefix).into();
+}
+
+/// Generates a call to Terraform method `endswith`, which is true if `s` ends with
+/// `suffix`.
+pub fn tf_endswith(stack: &Stack, s: impl ToFuncArg<String>, suffix: impl ToFuncArg<String>) -> PrimExpr<bool> {
+    return stack.func("endswith").a(s).a(suffix).into();
+}
+
+/// Generates a call to Terraform method `strcontains`, which is true if `s`
+/// contains `substr`.
+pub fn tf_strcontains(stack: &Stack, s: impl ToFuncArg<String>, substr: impl ToFuncArg<String>) -> PrimExpr<bool> {
+    return stack.func("strcontains").a(s).a(substr).into();
+}
+
+/// Generates a call to Terraform method `regex`, returning the first match of
+/// `pattern` in `s`. Fails when applying if there's no match. If the pattern has
+/// capture groups the result is a list (unnamed groups) or object (named groups)
+/// of the captures instead - use `stack.func` with the type you expect for those.
+pub fn tf_regex(stack: &Stack, pattern: &str, s: impl ToFuncArg<String>) -> PrimExpr<String> {
+    return stack.func("regex").a(pattern.to_string()).a(s).into();
+}
+
+/// Generates a call to Terraform method `regexall`, returning all matches of
+/// `pattern` in `s` (empty if there are none). As with `tf_regex`, patterns with
+/// capture groups produce a list of lists or objects instead.
+pub fn tf_regexall(stack: &Stack, pattern: &str, s: impl ToFuncArg<String>) -> ListRef<PrimExpr<String>> {
+    return stack.func("regexall").a(pattern.to_string()).a(s).into();
+}
+
+/// Generates a call to Terraform method `one`, which returns the only element of
+/// `list`, or `null` if it's empty (it fails if there's more than one). Attributes
+/// of a resource's `enabled_ref` use this to be `null` when the resource is
+/// disabled.
+pub fn tf_one<T: Ref>(stack: &Stack, list: &ListRef<T>) -> T {
+    return T::new(stack.shared.clone(), format!("one({})", list.base));
+}
+
+/// Generates a call to Terraform method `coalescelist`, which returns the first of
+/// `lists` that isn't empty.
+pub fn tf_coalescelist<T: Ref>(stack: &Stack, lists: &[&ListRef<T>]) -> ListRef<T> {
+    let mut f = stack.func("coalescelist");
+    for l in lists {
+        f = f.a_list(l);
+    }
+    return f.into();
+}
+
+/// Generates a call to Terraform method `compact`, which removes empty and `null`
+/// strings from `list`.
+pub fn tf_compact(stack: &Stack, list: &ListRef<PrimExpr<String>>) -> ListRef<PrimExpr<String>> {
+    return stack.func("compact").a_list(list).into();
+}
+
+/// Generates a call to Terraform method `setproduct`, which returns every
+/// combination of one element from each of `lists` (ex: for a resource per region
+/// and environment). Each combination is a list with an element from each input,
+/// in order.
+pub fn tf_setproduct<T: Ref>(stack: &Stack, lists: &[&ListRef<T>]) -> ListRef<ListRef<T>> {
+    let mut f = stack.func("setproduct");
+    for l in lists {
+        f = f.a_list(l);
+    }
+    return f.into();
+}
+
+/// Generates a call to Terraform method `zipmap`, which builds a map from a list of
+/// keys and a list of values of the same length.
+pub fn tf_zipmap<T: Ref>(stack: &Stack, keys: &ListRef<PrimExpr<String>>, values: &ListRef<T>) -> RecRef<T> {
+    return stack.func("zipmap").a_list(keys).a_list(values).into();
+}
+
+/// Generates a call to Terraform method `contains`, which is true if `list` has an
+/// element equal to `value`.
+pub fn tf_contains<T: PrimType>(
+    stack: &Stack,
+    list: &ListRef<PrimExpr<T>>,
+    value: impl ToFuncArg<T>,
+) -> PrimExpr<bool> {
+    return stack.func("contains").a_list(list).a(value).into();
+}
+
+/// Generates a call to Terraform method `index`, which returns the position of the
+/// first element of `list` equal to `value`. Fails when applying if there's no
+/// such element.
+pub fn tf_index<T: PrimType>(stack: &Stack, list: &ListRef<PrimExpr<T>>, value: impl ToFuncArg<T>) -> PrimExpr<i64> {
+    return stack.func("index").a_list(list).a(value).into();
+}
+
+/// Generates a call to Terraform method `anytrue`, which is true if any element of
+/// `list` is true (false for an empty list).
+pub fn tf_anytrue(stack: &Stack, list: &ListRef<PrimExpr<bool>>) -> PrimExpr<bool> {
+    return stack.func("anytrue").a_list(list).into();
+}
+
+/// Generates a call to Terraform method `alltrue`, which is true if every element of
+/// `list` is true (true for an empty list).
+pub fn tf_alltrue(stack: &Stack, list: &ListRef<PrimExpr<bool>>) -> PrimExpr<bool> {
+    return stack.func("alltrue").a_list(list).into();
+}
+
+/// Generates a call to Terraform method `templatestring` (Terraform 1.9 and newer),
+/// which renders a template stored elsewhere. `template` must be a reference to a
+/// string (ex: a variable or resource attribute), not a literal or other expression.
+/// `vars` must serialize to an object, and may contain expressions (ex: fields set
+/// from a `PrimExpr`). Fails if `vars` can't be serialized (ex: a map with
+/// non-string keys).
+pub fn tf_templatestring<T: Serialize>(
+    stack: &Stack,
+    template: PrimExpr<String>,
+    vars: &T,
+) -> Result<PrimExpr<String>, serde_json::Error> {
+    let replace_exprs = stack.shared.0.borrow().replace_exprs.clone();
+    let vars = AnyRef::new(stack.shared.clone(), value_to_expr(&serde_json::to_value(vars)?, &replace_exprs));
+    return Ok(stack.func("templatestring").a(template).a_any(&vars).into());
+}
+
+/// Converts json from serializing fields into Terraform expression syntax,
+/// replacing sentinels in strings with the expressions they stand for.
+fn value_to_expr(v: &Value, replace_exprs: &[(String, String)]) -> String {
+    match v {
+        Value::String(s) => {
+            let mut out = escape_template_literal(s);
+            for (k, v) in replace_exprs {
+                out = out.replace(k, v);
+            }
+            if out == "${null}" {
+                return "null".to_string();
+            }
+            return format!("\"{}\"", out);
+        },
+        Value::Array(a) => {
+            return format!(
+                "[{}]",
+                a.iter().map(|v| value_to_expr(v, replace_exprs)).collect::<Vec<_>>().join(", ")
+            );
+        },
+        Value::Object(o) => {
+            return format!(
+                "{{{}}}",
+                o
+                    .iter()
+                    .map(|(k, v)| format!("\"{}\" = {}", escape_template_literal(k), value_to_expr(v, replace_exprs)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        },
+        Value::Null | Value::Bool(_) | Value::Number(_) => {
+            return v.to_string();
+        },
+    }
+}
+
+/// Generates a call to Terraform provider function `provider::terraform::encode_tfvars`,
+/// which formats an object as the contents of a `.tfvars` file.
+pub fn tf_encode_tfvars(stack: &Stack, obj: &AnyRef) -> PrimExpr<String> {
+    return stack.terraform_func("encode_tfvars").a_any(obj).into();
+}
+
+/// Generates a call to Terraform provider function `provider::terraform::decode_tfvars`,
+/// which parses the contents of a `.tfvars` file into an object.
+pub fn tf_decode_tfvars(stack: &Stack, e: impl ToFuncArg<String>) -> AnyRef {
+    return stack.terraform_func("decode_tfvars").a(e).into();
+}
+
+/// Generates a call to Terraform provider function `provider::terraform::encode_expr`,
+/// which formats a value as Terraform expression syntax.
+pub fn tf_encode_expr(stack: &Stack, v: &AnyRef) -> PrimExpr<String> {
+    return stack.terraform_func("encode_expr").a_any(v).into();
+}
+
+/// Assembles a multipart MIME cloud-init user data document (like the
+/// `cloudinit_config` datasource) from parts such as shell scripts
+/// (`text/x-shellscript`) and cloud configs (`text/cloud-config`). Parts must not
+/// contain the line `--MIMEBOUNDARY`.
+#[derive(Default)]
+pub struct CloudInit {
+    // Escaped template text
+    parts: Vec<(String, String)>,
+}
+
+impl CloudInit {
+    /// Start an empty document, add parts with `part` and `part_expr`.
+    pub fn new() -> Self {
+        return Default::default();
+    }
+
+    /// Add a part with literal content.
+    pub fn part(mut self, content_type: &str, content: impl ToString) -> Self {
+        self.parts.push((content_type.to_string(), escape_template_literal(&content.to_string())));
+        return self;
+    }
+
+    /// Add a part with content computed by an expression.
+    pub fn part_expr(mut self, content_type: &str, content: &PrimExpr<String>) -> Self {
+        self.parts.push((content_type.to_string(), format!("${{{}}}", content.1)));
+        return self;
+    }
+
+    /// Produce the document as a plain string.
+    pub fn to_expr(&self, stack: &Stack) -> PrimExpr<String> {
+        let mut out = escape_template_literal(
+            "Content-Type: multipart/mixed; boundary=\"MIMEBOUNDARY\"\r\nMIME-Version: 1.0\r\n\r\n",
+        );
+        for (content_type, content) in &self.parts {
+            out.push_str(
+                &escape_template_literal(
+                    &format!(
+                        "--MIMEBOUNDARY\r\nContent-Transfer-Encoding: 7bit\r\nContent-Type: {}\r\nMime-Version: 1.0\r\n\r\n",
+                        content_type
+                    ),
+                ),
+            );
+            out.push_str(content);
+            out.push_str(&escape_template_literal("\r\n"));
+        }
+        out.push_str(&escape_template_literal("--MIMEBOUNDARY--\r\n"));
+        return stack.expr(format!("\"{}\"", out));
+    }
+
+    /// Produce the document base64 encoded, for attributes like `user_data_base64`.
+    pub fn to_base64(&self, stack: &Stack) -> PrimExpr<String> {
+        return stack.func("base64encode").a(self.to_expr(stack)).into();
+    }
+
+    /// Produce the document gzipped and base64 encoded, for large documents.
+    pub fn to_base64_gzip(&self, stack: &Stack) -> PrimExpr<String> {
+        return stack.func("base64gzip").a(self.to_expr(stack)).into();
+    }
+}
 
 /// Gets the path to the Terraform / OpenTofu binary.
 pub fn get_terraform_binary() -> String {
@@ -51,3 +316,203 @@ pub fn get_terraform_binary() -> String {
     "terraform".to_string()
 }
 
+/// Formats a duration as a number of seconds with an `s` suffix (ex: `90s`,
+/// `0.5s`), which is accepted by both Go's `time.ParseDuration` and the protobuf
+/// duration format used by most providers.
+pub fn format_duration(d: std::time::Duration) -> String {
+    if d.subsec_nanos() == 0 {
+        return format!("{}s", d.as_secs());
+    }
+    let frac = format!("{:09}", d.subsec_nanos());
+    return format!("{}.{}s", d.as_secs(), frac.trim_end_matches('0'));
+}
+
+/// Formats a number of bytes as a quantity with the largest binary suffix that
+/// represents it exactly (ex: `10Gi`, `1536Ki`, `100`), as used by Kubernetes.
+// `u64::is_multiple_of` needs Rust 1.87
+#[allow(unknown_lints, clippy::manual_is_multiple_of)]
+pub fn format_size(bytes: u64) -> String {
+    for (i, suffix) in ["Ei", "Pi", "Ti", "Gi", "Mi", "Ki"].iter().enumerate() {
+        let unit = 1u64 << (10 * (6 - i));
+        if bytes != 0 && bytes % unit == 0 {
+            return format!("{}{}", bytes / unit, suffix);
+        }
+    }
+    return bytes.to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BuildStack,
+        PrimExpr,
+        PrimField,
+    };
+    use super::*;
+
+    fn list<T: Ref>(stack: &Stack, base: &str) -> ListRef<T> {
+        return ListRef::new(stack.shared.clone(), base.to_string());
+    }
+
+    #[test]
+    fn cloud_init() {
+        let stack = BuildStack {}.build();
+        let script: PrimExpr<String> = stack.expr("var.script");
+        let doc =
+            CloudInit::new()
+                .part("text/cloud-config", "packages: [\"${pkg}\"]")
+                .part_expr("text/x-shellscript", &script);
+        let expected =
+            concat!(
+                r#""Content-Type: multipart/mixed; boundary=\"MIMEBOUNDARY\"\r\nMIME-Version: 1.0\r\n\r\n"#,
+                r#"--MIMEBOUNDARY\r\nContent-Transfer-Encoding: 7bit\r\nContent-Type: text/cloud-config\r\n"#,
+                r#"Mime-Version: 1.0\r\n\r\npackages: [\"$${pkg}\"]\r\n"#,
+                r#"--MIMEBOUNDARY\r\nContent-Transfer-Encoding: 7bit\r\nContent-Type: text/x-shellscript\r\n"#,
+                r#"Mime-Version: 1.0\r\n\r\n${var.script}\r\n"#,
+                r#"--MIMEBOUNDARY--\r\n""#
+            );
+        assert_eq!(doc.to_expr(&stack).raw(), expected);
+        assert_eq!(doc.to_base64(&stack).raw(), format!("base64encode({})", expected));
+        assert_eq!(doc.to_base64_gzip(&stack).raw(), format!("base64gzip({})", expected));
+    }
+
+    #[test]
+    fn regex() {
+        let stack = BuildStack {}.build();
+        let name = || -> PrimExpr<String> {
+            stack.expr("var.name")
+        };
+        assert_eq!(tf_regex(&stack, r#"^([a-z]+)-\d+"${x}$"#, name()).raw(), r#"regex("^([a-z]+)-\\d+\"$${x}$", var.name)"#);
+        assert_eq!(tf_regexall(&stack, "[0-9]+", name()).base, r#"regexall("[0-9]+", var.name)"#);
+    }
+
+    #[test]
+    fn one() {
+        let stack = BuildStack {}.build();
+        let ids: ListRef<PrimExpr<String>> = list(&stack, "aws_instance.x[*].id");
+        assert_eq!(tf_one(&stack, &ids).raw(), "one(aws_instance.x[*].id)");
+        let blocks: ListRef<RecRef<PrimExpr<String>>> = list(&stack, "var.blocks");
+        assert_eq!(tf_one(&stack, &blocks).get("k").raw(), "one(var.blocks)[\"k\"]");
+    }
+
+    #[test]
+    fn coalescelist_compact() {
+        let stack = BuildStack {}.build();
+        let a: ListRef<PrimExpr<String>> = list(&stack, "var.a");
+        let b: ListRef<PrimExpr<String>> = list(&stack, "var.b");
+        assert_eq!(tf_coalescelist(&stack, &[&a, &b]).base, "coalescelist(var.a, var.b)");
+        assert_eq!(tf_compact(&stack, &a).base, "compact(var.a)");
+        assert_eq!(tf_compact(&stack, &tf_coalescelist(&stack, &[&a, &b])).get(0).raw(), "compact(coalescelist(var.a, var.b))[0]");
+    }
+
+    #[test]
+    fn collection_predicates() {
+        let stack = BuildStack {}.build();
+        let names: ListRef<PrimExpr<String>> = list(&stack, "var.names");
+        let flags: ListRef<PrimExpr<bool>> = list(&stack, "var.flags");
+        assert_eq!(tf_contains(&stack, &names, "a\"b".to_string()).raw(), "contains(var.names, \"a\\\"b\")");
+        assert_eq!(tf_index(&stack, &names, stack.expr::<String>("var.name")).raw(), "index(var.names, var.name)");
+        assert_eq!(tf_contains(&stack, &list::<PrimExpr<i64>>(&stack, "var.ports"), 443).raw(), "contains(var.ports, 443)");
+        assert_eq!(tf_anytrue(&stack, &flags).raw(), "anytrue(var.flags)");
+        assert_eq!(tf_alltrue(&stack, &flags).raw(), "alltrue(var.flags)");
+    }
+
+    #[test]
+    fn string_predicates() {
+        let stack = BuildStack {}.build();
+        let name = || -> PrimExpr<String> {
+            stack.expr("var.name")
+        };
+        assert_eq!(tf_startswith(&stack, name(), "arn:\"${x}".to_string()).raw(), "startswith(var.name, \"arn:\\\"$${x}\")");
+        assert_eq!(tf_endswith(&stack, name(), name()).raw(), "endswith(var.name, var.name)");
+        assert_eq!(tf_strcontains(&stack, "abc".to_string(), name()).raw(), "strcontains(\"abc\", var.name)");
+    }
+
+    #[test]
+    fn setproduct_zipmap() {
+        let stack = BuildStack {}.build();
+        let regions = list::<PrimExpr<String>>(&stack, "var.regions");
+        let envs = list::<PrimExpr<String>>(&stack, "var.envs");
+        let product = tf_setproduct(&stack, &[&regions, &envs]);
+        assert_eq!(product.get(0).get(1).raw(), "setproduct(var.regions, var.envs)[0][1]");
+        assert_eq!(tf_setproduct(&stack, &[&regions]).get(0).get(0).raw(), "setproduct(var.regions)[0][0]");
+        let ports = list::<PrimExpr<i64>>(&stack, "var.ports");
+        assert_eq!(tf_zipmap(&stack, &regions, &ports).get("a").raw(), "zipmap(var.regions, var.ports)[\"a\"]");
+    }
+
+    #[test]
+    fn gzip_and_file_hashes() {
+        let stack = BuildStack {}.build();
+        assert_eq!(tf_base64gzip(&stack, "a\"${b}".to_string()).raw(), "base64gzip(\"a\\\"$${b}\")");
+        assert_eq!(tf_base64gzip(&stack, stack.expr::<String>("var.doc")).raw(), "base64gzip(var.doc)");
+        let path = || -> PrimExpr<String> { stack.expr("\"${path.module}/init.sh\"") };
+        assert_eq!(tf_filemd5(&stack, path()).raw(), "filemd5(\"${path.module}/init.sh\")");
+        assert_eq!(tf_filesha256(&stack, "init.sh".to_string()).raw(), "filesha256(\"init.sh\")");
+        assert_eq!(tf_filesha512(&stack, path()).raw(), "filesha512(\"${path.module}/init.sh\")");
+    }
+
+    #[test]
+    fn templatestring() {
+        #[derive(Serialize)]
+        struct Server {
+            host: PrimField<String>,
+            port: PrimField<i64>,
+            tags: Vec<PrimField<String>>,
+        }
+
+        #[derive(Serialize)]
+        struct Vars {
+            name: PrimField<String>,
+            greeting: PrimField<String>,
+            missing: PrimField<String>,
+            server: Server,
+        }
+
+        let stack = BuildStack {}.build();
+        let vars = Vars {
+            name: stack.expr::<String>("var.name").into(),
+            greeting: "hi \"${name}\" %{if x}\n".into(),
+            missing: stack.null::<String>().into(),
+            server: Server {
+                host: "h${x}".into(),
+                port: 80.into(),
+                tags: vec!["a".into(), stack.expr::<String>("var.tag").into()],
+            },
+        };
+        assert_eq!(
+            tf_templatestring(&stack, stack.expr("var.template"), &vars).unwrap().raw(),
+            concat!(
+                r#"templatestring(var.template, {"greeting" = "hi \"$${name}\" %%{if x}\n", "missing" = null, "#,
+                r#""name" = "${var.name}", "server" = {"host" = "h$${x}", "port" = 80, "tags" = ["a", "${var.tag}"]}})"#
+            )
+        );
+        let bad_vars = std::collections::HashMap::from([((1, 2), "x")]);
+        assert!(tf_templatestring(&stack, stack.expr("var.template"), &bad_vars).is_err());
+    }
+
+    #[test]
+    fn value_to_expr_escapes_keys() {
+        let stack = BuildStack {}.build();
+        let expr = stack.shared.add_sentinel("var.x");
+        let replace_exprs = stack.shared.0.borrow().replace_exprs.clone();
+        assert_eq!(
+            value_to_expr(&serde_json::json!({
+                "a\"${b}": [expr, null, true, 1.5, {}]
+            }), &replace_exprs),
+            r#"{"a\"$${b}" = ["${var.x}", null, true, 1.5, {}]}"#
+        );
+    }
+
+    #[test]
+    fn format_duration_size() {
+        assert_eq!(format_duration(std::time::Duration::from_secs(90)), "90s");
+        assert_eq!(format_duration(std::time::Duration::from_millis(500)), "0.5s");
+        assert_eq!(format_duration(std::time::Duration::new(1, 1)), "1.000000001s");
+        assert_eq!(format_duration(std::time::Duration::ZERO), "0s");
+        assert_eq!(format_size(10 << 30), "10Gi");
+        assert_eq!(format_size(1536 << 10), "1536Ki");
+        assert_eq!(format_size(1 << 60), "1Ei");
+        assert_eq!(format_size(100), "100");
+        assert_eq!(format_size(0), "0");
+    }
+}
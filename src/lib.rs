@@ -14,6 +14,7 @@ use std::{
     },
     io::{
         self,
+        Read,
         Write,
     },
     path::{
@@ -27,7 +28,9 @@ use std::{
     },
     rc::Rc,
     str::FromStr,
+    sync::Arc,
     marker::PhantomData,
+    time::Duration,
 };
 use serde::{
     de::DeserializeOwned,
@@ -55,6 +58,15 @@ pub mod set_field;
 pub mod set_ref;
 pub mod variable;
 pub mod helpers;
+pub mod any_field;
+pub mod any_ref;
+pub mod sourceschema;
+pub mod diff;
+pub mod time_sleep;
+pub mod module_ref;
+pub mod module;
+pub mod state;
+pub mod prelude;
 
 pub use ref_::*;
 pub use expr::*;
@@ -68,9 +80,26 @@ pub use prim_field::*;
 pub use prim_ref::*;
 pub use set_field::*;
 pub use set_ref::*;
-use utils::REPLACE_EXPRS;
+use utils::ReplaceExprsGuard;
 pub use variable::*;
 pub use helpers::*;
+pub use any_field::*;
+pub use any_ref::*;
+pub use diff::*;
+pub use time_sleep::*;
+pub use module_ref::*;
+pub use module::*;
+pub use state::*;
+
+/// CLI config written to the run directory when `RunOptions::provider_mirror` is
+/// set.
+const CLI_CONFIG_NAME: &str = "terrars.tfrc";
+
+/// The stack config written to the run directory.
+const CONFIG_NAME: &str = "stack.tf.json";
+
+/// The local backend state file in the run directory.
+const STATE_NAME: &str = "state.tfstate";
 
 /// Use this to create a new stack.
 pub struct BuildStack {}
@@ -82,8 +111,18 @@ impl BuildStack {
             variables: Default::default(),
             datasources: Default::default(),
             resources: Default::default(),
+            ephemerals: Default::default(),
+            modules: Default::default(),
             outputs: Default::default(),
-            shared: StackShared(Rc::new(RefCell::new(StackShared_ { replace_exprs: Default::default() }))),
+            moved: Default::default(),
+            imports: Default::default(),
+            terraform_settings: Default::default(),
+            backend: None,
+            emit_required_providers: true,
+            shared: StackShared(Rc::new(RefCell::new(StackShared_ {
+                replace_exprs: Default::default(),
+                uses_terraform_provider: false,
+            }))),
         };
     }
 }
@@ -95,6 +134,8 @@ pub enum ComponentType {
     Variable,
     Datasource,
     Resource,
+    Ephemeral,
+    Module,
     Output,
 }
 
@@ -102,33 +143,248 @@ pub enum ComponentType {
 pub enum StackError {
     #[error("Duplicate {0:?} with tf_id {1}")]
     Duplicate(ComponentType, String),
+    #[error(
+        "Invalid {0:?} tf_id {1:?}; ids must start with a letter or underscore and contain only letters, digits, underscores, and dashes"
+    )]
+    InvalidId(ComponentType, String),
+    #[error("A stack can't have both a `cloud` block and a `backend`")]
+    CloudWithBackend,
+    #[error("Failed to write stack config")]
+    WriteError(#[source] io::Error),
+}
+
+/// Which HCP Terraform workspace(s) a stack using `Stack::set_cloud` runs in.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloudWorkspaces {
+    /// A single workspace with this name
+    Name(String),
+    /// All workspaces with these tags, selected with the `workspace` run option
+    Tags(Vec<String>),
+}
+
+/// Where Terraform stores a stack's state, set with `Stack::set_backend`. Other
+/// backends can be configured with `Stack::set_terraform_setting("backend", ...)`.
+#[derive(Clone, Debug)]
+pub enum Backend {
+    /// A local file. Relative paths are relative to the directory Terraform is run in.
+    /// Without a backend the state is stored at the `state_path` passed to
+    /// `serialize`.
+    Local {
+        path: PathBuf,
+    },
+    /// An S3 object. Credentials are read from the environment like for the AWS
+    /// provider.
+    S3 {
+        bucket: String,
+        /// The object key
+        key: String,
+        region: String,
+        /// Lock the state using this DynamoDB table, which needs a `LockID` string
+        /// partition key
+        dynamodb_table: Option<String>,
+        /// Lock the state using a lock file in the bucket, in Terraform 1.10 and newer
+        use_lockfile: bool,
+        /// Enable server-side encryption of the state object
+        encrypt: bool,
+    },
+    /// Objects in a Google Cloud Storage bucket.
+    Gcs {
+        bucket: String,
+        /// The directory in the bucket, with one state object per workspace
+        prefix: String,
+        /// A service account key (the json or a path to it). Without this, Application
+        /// Default Credentials are used.
+        credentials: Option<String>,
+    },
+}
+
+/// A command that exited unsuccessfully.
+#[derive(Clone, Debug, Serialize)]
+pub struct CommandFailure {
+    pub program: String,
+    pub args: Vec<String>,
+    /// The directory the command was run in, if set
+    pub dir: Option<PathBuf>,
+    /// The exit code, missing if the command was terminated by a signal
+    pub code: Option<i32>,
+    /// Everything the command wrote to stderr (ex: Terraform's error messages),
+    /// without color codes
+    pub stderr: String,
+}
+
+impl CommandFailure {
+    fn new(command: &Command, status: process::ExitStatus, stderr: &[u8]) -> Self {
+        CommandFailure {
+            program: command.get_program().to_string_lossy().to_string(),
+            args: command.get_args().map(|a| a.to_string_lossy().to_string()).collect(),
+            dir: command.get_current_dir().map(Path::to_path_buf),
+            code: status.code(),
+            stderr: strip_ansi(&String::from_utf8_lossy(stderr)),
+        }
+    }
+}
+
+impl std::fmt::Display for CommandFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Command `{}", self.program)?;
+        for a in &self.args {
+            write!(f, " {}", a)?;
+        }
+        write!(f, "`")?;
+        if let Some(dir) = &self.dir {
+            write!(f, " in {}", dir.to_string_lossy())?;
+        }
+        match self.code {
+            Some(code) => write!(f, " failed with exit code {}", code)?,
+            None => write!(f, " was terminated by a signal")?,
+        }
+        let stderr = self.stderr.trim();
+        if !stderr.is_empty() {
+            write!(f, ":\n{}", stderr)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Error, Debug)]
 pub enum RunError {
-    #[error("Failed to prepare run directory {0:?}: {1:?}")]
-    FsError(PathBuf, io::Error),
-    #[error("Error serializing stack: {0:?}")]
+    #[error("Failed to prepare run directory {0:?}")]
+    FsError(PathBuf, #[source] io::Error),
+    #[error("Error serializing stack")]
     StackError(
         #[from]
         StackError,
     ),
-    #[error("Failed to write configs: {0:?}")]
+    #[error("Failed to write configs or run command")]
     FileError(
         #[from]
         io::Error,
     ),
-    #[error("Failed to write or parse json: {0:?}")]
+    #[error("Failed to write or parse json")]
     JsonError(
         #[from]
         serde_json::Error,
     ),
-    #[error("Command {0:?} failed with result {1:?}")]
-    CommandError(Command, process::ExitStatus),
+    #[error("{0}")]
+    CommandError(CommandFailure),
+    #[error("Provider {0} was generated for version {1} but version {2} is installed")]
+    ProviderVersionMismatch(String, String, String),
+    #[error("Local source of module {0} doesn't exist relative to the run directory: {1:?}")]
+    ModuleSourceMissing(String, PathBuf),
+    #[error("Run in temporary directory {0:?} failed, the directory was kept")]
+    TempDirRunError(PathBuf, #[source] Box<RunError>),
+}
+
+/// What to do if the provider versions Terraform installed differ from the versions
+/// the provider bindings were generated for.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ProviderVersionCheck {
+    /// Don't check
+    #[default]
+    Off,
+    /// Pass each mismatch to `RunOptions::provider_version_warning`, or print a
+    /// warning to stderr if it isn't set
+    Warn,
+    /// Fail with `RunError::ProviderVersionMismatch`
+    Error,
+}
+
+/// Receives provider version mismatches found with `ProviderVersionCheck::Warn`:
+/// the provider source, the version the bindings were generated for, and the
+/// installed version.
+#[derive(Clone)]
+pub struct ProviderVersionWarning(Arc<ProviderVersionWarningFn>);
+
+type ProviderVersionWarningFn = dyn Fn(&str, &str, &str) + Send + Sync;
+
+impl ProviderVersionWarning {
+    pub fn new(f: impl Fn(&str, &str, &str) + Send + Sync + 'static) -> Self {
+        ProviderVersionWarning(Arc::new(f))
+    }
+}
+
+impl std::fmt::Debug for ProviderVersionWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProviderVersionWarning")
+    }
+}
+
+/// Additional settings for `Stack::run_with_options`.
+#[derive(Clone, Debug)]
+pub struct RunOptions {
+    /// Compare the versions in the Terraform lock file with the versions the provider
+    /// bindings were generated for, before running the command.
+    pub provider_version_check: ProviderVersionCheck,
+    /// Where to send mismatches found with `ProviderVersionCheck::Warn`, for instance
+    /// to log them or to ignore some providers. If not set they're printed to stderr.
+    pub provider_version_warning: Option<ProviderVersionWarning>,
+    /// Share downloaded providers between runs by setting `TF_PLUGIN_CACHE_DIR`. The
+    /// directory is created if it doesn't exist.
+    pub plugin_cache_dir: Option<PathBuf>,
+    /// Install providers only from this filesystem mirror directory (ex: one created
+    /// with `terraform providers mirror`) rather than downloading them. This writes a
+    /// CLI config file to the run directory and points `TF_CLI_CONFIG_FILE` at it,
+    /// replacing any other CLI config.
+    pub provider_mirror: Option<PathBuf>,
+    /// Lock the state while running `plan`, `apply`, `destroy`, and `refresh`.
+    /// Disable (`-lock=false`) to run despite a stale lock from a crashed run.
+    /// Defaults to true.
+    pub lock: bool,
+    /// How long to retry acquiring the state lock (`-lock-timeout`).
+    pub lock_timeout: Option<Duration>,
+    /// Select this Terraform workspace before running, creating it if it doesn't
+    /// exist. Each workspace's state is stored separately in
+    /// `terraform.tfstate.d/<workspace>/` next to the default workspace's state. The
+    /// workspace stays selected for later commands in the same directory (ex:
+    /// `get_output`).
+    pub workspace: Option<String>,
+    /// Limit `plan`, `apply`, `destroy`, and `refresh` to these resource addresses
+    /// and their dependencies (`-target`). Get addresses with `extract_ref` (ex:
+    /// `bucket.extract_ref()` or `bucket.instance("logs").extract_ref()`).
+    pub targets: Vec<String>,
+    /// Replace these resources even if they haven't changed (`-replace`) during
+    /// `plan` and `apply`. Addresses are as for `targets`.
+    pub replace: Vec<String>,
+    /// Where to create temporary files (ex: the variables file) and, for
+    /// `run_in_tempdir`, the run directory. Defaults to the system temporary
+    /// directory.
+    pub temp_dir: Option<PathBuf>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            provider_version_check: Default::default(),
+            provider_version_warning: None,
+            plugin_cache_dir: None,
+            provider_mirror: None,
+            lock: true,
+            lock_timeout: None,
+            workspace: None,
+            targets: vec![],
+            replace: vec![],
+            temp_dir: None,
+        }
+    }
+}
+
+/// Where `Stack::run_with_options` put things.
+#[derive(Clone, Debug)]
+pub struct RunResult {
+    /// The directory Terraform was run in
+    pub work_dir: PathBuf,
+    /// The stack config written to `work_dir`
+    pub config_path: PathBuf,
+    /// The local state file for the selected workspace. This doesn't exist if the
+    /// stack uses `set_cloud` or a remote backend, or hasn't been applied yet.
+    pub state_path: PathBuf,
 }
 
 struct StackShared_ {
     replace_exprs: Vec<(String, String)>,
+    // The built-in `terraform` provider must be declared to use its functions
+    uses_terraform_provider: bool,
 }
 
 #[derive(Clone)]
@@ -148,7 +404,17 @@ pub struct Stack {
     variables: Vec<Rc<dyn VariableTrait>>,
     datasources: Vec<Rc<dyn Datasource_>>,
     resources: Vec<Rc<dyn Resource_>>,
+    ephemerals: Vec<Rc<dyn Ephemeral_>>,
+    modules: Vec<Rc<dyn Module>>,
     outputs: Vec<Rc<dyn Output>>,
+    // (from, to) addresses
+    moved: Vec<(String, String)>,
+    // (to address, id)
+    imports: Vec<(String, PrimField<String>)>,
+    // Additional keys in the `terraform` block
+    terraform_settings: BTreeMap<String, Value>,
+    backend: Option<Backend>,
+    emit_required_providers: bool,
     pub shared: StackShared,
 }
 
@@ -162,12 +428,115 @@ impl Stack {
         PrimExpr(self.shared.clone(), expr.to_expr_raw(), Default::default())
     }
 
+    /// The expression `true`.
+    pub fn true_(&self) -> PrimExpr<bool> {
+        self.expr_lit(true)
+    }
+
+    /// The expression `false`.
+    pub fn false_(&self) -> PrimExpr<bool> {
+        self.expr_lit(false)
+    }
+
+    /// The expression `null`, for instance to explicitly unset an attribute or in a
+    /// conditional. Fields set to only this are written as a bare `null`.
+    pub fn null<T: PrimType>(&self) -> PrimExpr<T> {
+        self.expr("null")
+    }
+
+    /// Build an object expression (`{ "k1" = v1, "k2" = v2 }`) from keys and
+    /// expressions, for instance to pass a map of computed values to a function.
+    /// Keys are escaped.
+    pub fn object<T: PrimType>(&self, pairs: Vec<(impl ToString, PrimExpr<T>)>) -> RecRef<PrimExpr<T>> {
+        let mut out = String::from("{");
+        for (i, (k, v)) in pairs.into_iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            out.push_str(&format!("{} = {}", k.to_string().to_expr_raw(), v.1));
+        }
+        out.push('}');
+        RecRef::new(self.shared.clone(), out)
+    }
+
+    /// Build a tuple expression (`[v1, v2]`) from expressions, usable as a list.
+    pub fn tuple<T: PrimType>(&self, items: Vec<PrimExpr<T>>) -> ListRef<PrimExpr<T>> {
+        ListRef::new(self.shared.clone(), format!("[{}]", items.iter().map(|v| v.1.as_str()).collect::<Vec<_>>().join(", ")))
+    }
+
+    /// The current instance's key (`each.key`). Only valid in attributes of a
+    /// resource created with `for_each` (ex: with `map`), where `MapKV::key` is usually
+    /// more convenient.
+    pub fn each_key(&self) -> PrimExpr<String> {
+        self.expr("each.key")
+    }
+
+    /// The current instance's value (`each.value`). Only valid in attributes of a
+    /// resource created with `for_each`, like `each_key`.
+    pub fn each_value<T: Ref>(&self) -> T {
+        T::new(self.shared.clone(), "each.value".into())
+    }
+
+    /// The current instance's index (`count.index`). Only valid in attributes of a
+    /// resource with `set_count`.
+    pub fn count_index(&self) -> PrimExpr<i64> {
+        self.expr("count.index")
+    }
+
     /// Turn a raw expression string into a `PrimExpr` - the string must be properly
     /// escaped, etc.
     pub fn expr<T: PrimType>(&self, expr: impl ToString) -> PrimExpr<T> {
         PrimExpr(self.shared.clone(), expr.to_string(), Default::default())
     }
 
+    /// Build a string expression from a template with named placeholders, like
+    /// `format_id("arn:aws:s3:::{bucket}/*", &[("bucket", bucket.bucket())])`. Use
+    /// `{{` and `}}` for literal braces. Panics if the template is malformed or uses a
+    /// placeholder missing from `parts`.
+    pub fn format_id(&self, template: &str, parts: &[(&str, PrimExpr<String>)]) -> PrimExpr<String> {
+        let mut out = String::new();
+        out.push('"');
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                },
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                },
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => panic!("Unterminated placeholder in format template [{}]", template),
+                        }
+                    }
+                    let part =
+                        parts
+                            .iter()
+                            .find(|(k, _)| *k == name)
+                            .unwrap_or_else(
+                                || panic!("Missing part for placeholder [{}] in format template [{}]", name, template),
+                            );
+                    out.push_str(&escape_template_literal(&literal));
+                    literal.clear();
+                    out.push_str(&format!("${{{}}}", part.1.1));
+                },
+                '}' => panic!("Unmatched }} in format template [{}]", template),
+                c => literal.push(c),
+            }
+        }
+        out.push_str(&escape_template_literal(&literal));
+        out.push('"');
+        PrimExpr(self.shared.clone(), out, Default::default())
+    }
+
     /// Start a new function call expression
     pub fn func(&self, name: &str) -> Func {
         Func {
@@ -177,11 +546,81 @@ impl Stack {
         }
     }
 
+    /// Start a new call expression for a provider-defined function (ex:
+    /// `provider_func("aws", "arn_parse")` for `provider::aws::arn_parse`). The
+    /// provider must be used in the stack.
+    pub fn provider_func(&self, provider: &str, name: &str) -> Func {
+        self.func(&format!("provider::{}::{}", provider, name))
+    }
+
+    /// Start a new call expression for a function of the built-in `terraform`
+    /// provider (ex: `encode_tfvars`). This adds the provider to
+    /// `required_providers`.
+    pub fn terraform_func(&self, name: &str) -> Func {
+        self.shared.0.borrow_mut().uses_terraform_provider = true;
+        self.provider_func("terraform", name)
+    }
+
     /// Convert the stack to json bytes.
     pub fn serialize(&self, state_path: &Path) -> Result<Vec<u8>, StackError> {
-        REPLACE_EXPRS.with(move |f| {
-            *f.borrow_mut() = Some(self.shared.0.borrow().replace_exprs.clone());
-        });
+        self.serialize_with_formatter(state_path, serde_json::ser::PrettyFormatter::new())
+    }
+
+    /// Like `serialize` but with control over the json formatting, for instance
+    /// `PrettyFormatter::with_indent(b"    ")` for 4-space indentation or
+    /// `CompactFormatter`.
+    pub fn serialize_with_formatter(
+        &self,
+        state_path: &Path,
+        formatter: impl serde_json::ser::Formatter,
+    ) -> Result<Vec<u8>, StackError> {
+        let mut res = vec![];
+        self.write_to_with_formatter(&mut res, state_path, formatter)?;
+        Ok(res)
+    }
+
+    /// Like `serialize` but writes the json to `writer` (ex: stdout) as it's
+    /// produced.
+    pub fn write_to(&self, writer: &mut impl Write, state_path: &Path) -> Result<(), StackError> {
+        self.write_to_with_formatter(writer, state_path, serde_json::ser::PrettyFormatter::new())
+    }
+
+    /// Like `write_to` but with control over the json formatting, as for
+    /// `serialize_with_formatter`.
+    pub fn write_to_with_formatter(
+        &self,
+        writer: &mut impl Write,
+        state_path: &Path,
+        formatter: impl serde_json::ser::Formatter,
+    ) -> Result<(), StackError> {
+        self.write_filtered(writer, state_path, formatter, &|_, _| true)
+    }
+
+    /// Like `serialize` but only includes the variables, datasources, resources,
+    /// ephemeral resources, modules, and outputs for which `filter` returns true,
+    /// given the component type and tf_id. `moved` and `import` blocks are included
+    /// if their `to` address is. Providers are always included. For debugging, for
+    /// instance to make a minimal config reproducing a problem with one resource.
+    /// References to excluded components aren't checked, so Terraform may reject the
+    /// result.
+    pub fn serialize_filtered(
+        &self,
+        state_path: &Path,
+        filter: impl Fn(&ComponentType, &str) -> bool,
+    ) -> Result<Vec<u8>, StackError> {
+        let mut res = vec![];
+        self.write_filtered(&mut res, state_path, serde_json::ser::PrettyFormatter::new(), &filter)?;
+        Ok(res)
+    }
+
+    fn write_filtered(
+        &self,
+        writer: &mut impl Write,
+        state_path: &Path,
+        formatter: impl serde_json::ser::Formatter,
+        filter: &dyn Fn(&ComponentType, &str) -> bool,
+    ) -> Result<(), StackError> {
+        let replace_exprs = ReplaceExprsGuard::new(self.shared.0.borrow().replace_exprs.clone());
         let mut required_providers = BTreeMap::new();
         for p in &self.providers {
             match required_providers.entry(p.extract_type_tf_id()) {
@@ -191,18 +630,31 @@ impl Stack {
                 std::collections::btree_map::Entry::Occupied(_) => { },
             };
         }
+        if self.shared.0.borrow().uses_terraform_provider {
+            required_providers
+                .entry("terraform".to_string())
+                .or_insert_with(|| json!({
+                    "source": "terraform.io/builtin/terraform"
+                }));
+        }
         let mut providers = BTreeMap::new();
         for p in &self.providers {
             providers.entry(p.extract_type_tf_id()).or_insert_with(Vec::new).push(p.extract_provider());
         }
         let mut variables = BTreeMap::new();
         for v in &self.variables {
+            if !filter(&ComponentType::Variable, &v.extract_tf_id()) {
+                continue;
+            }
             if variables.insert(v.extract_tf_id(), v.extract_value()).is_some() {
                 Err(StackError::Duplicate(ComponentType::Variable, v.extract_tf_id()))?;
             }
         }
         let mut data = BTreeMap::new();
         for d in &self.datasources {
+            if !filter(&ComponentType::Datasource, &d.extract_tf_id()) {
+                continue;
+            }
             if data
                 .entry(d.extract_datasource_type())
                 .or_insert_with(BTreeMap::new)
@@ -213,6 +665,9 @@ impl Stack {
         }
         let mut resources = BTreeMap::new();
         for r in &self.resources {
+            if !filter(&ComponentType::Resource, &r.extract_tf_id()) {
+                continue;
+            }
             if resources
                 .entry(r.extract_resource_type())
                 .or_insert_with(BTreeMap::new)
@@ -221,21 +676,88 @@ impl Stack {
                 Err(StackError::Duplicate(ComponentType::Resource, r.extract_tf_id()))?;
             }
         }
+        let mut ephemerals = BTreeMap::new();
+        for e in &self.ephemerals {
+            if !filter(&ComponentType::Ephemeral, &e.extract_tf_id()) {
+                continue;
+            }
+            if ephemerals
+                .entry(e.extract_ephemeral_type())
+                .or_insert_with(BTreeMap::new)
+                .insert(e.extract_tf_id(), e.extract_value())
+                .is_some() {
+                Err(StackError::Duplicate(ComponentType::Ephemeral, e.extract_tf_id()))?;
+            }
+        }
+        let mut modules = BTreeMap::new();
+        for m in &self.modules {
+            if !filter(&ComponentType::Module, &m.extract_tf_id()) {
+                continue;
+            }
+            if modules.insert(m.extract_tf_id(), m.extract_value()).is_some() {
+                Err(StackError::Duplicate(ComponentType::Module, m.extract_tf_id()))?;
+            }
+        }
         let mut outputs = BTreeMap::new();
         for o in &self.outputs {
+            if !filter(&ComponentType::Output, &o.extract_tf_id()) {
+                continue;
+            }
             if outputs.insert(o.extract_tf_id(), o.extract_value()).is_some() {
                 Err(StackError::Duplicate(ComponentType::Output, o.extract_tf_id()))?;
             }
         }
-        let mut out = BTreeMap::new();
-        out.insert("terraform", json!({
-            "backend": {
-                "local": {
-                    "path": state_path.to_string_lossy(),
+        let mut terraform = serde_json::Map::new();
+        if self.terraform_settings.contains_key("cloud") {
+            if self.terraform_settings.contains_key("backend") || self.backend.is_some() {
+                return Err(StackError::CloudWithBackend);
+            }
+        } else {
+            let backend = match &self.backend {
+                None => local_backend(state_path),
+                Some(Backend::Local { path }) => local_backend(path),
+                Some(Backend::S3 { bucket, key, region, dynamodb_table, use_lockfile, encrypt }) => {
+                    let mut s3 = json!({
+                        "bucket": bucket,
+                        "key": key,
+                        "region": region,
+                    });
+                    if let Some(table) = dynamodb_table {
+                        s3["dynamodb_table"] = json!(table);
+                    }
+                    if *use_lockfile {
+                        s3["use_lockfile"] = json!(true);
+                    }
+                    if *encrypt {
+                        s3["encrypt"] = json!(true);
+                    }
+                    json!({
+                        "s3": s3
+                    })
                 },
-            },
-            "required_providers": required_providers,
-        }));
+                Some(Backend::Gcs { bucket, prefix, credentials }) => {
+                    let mut gcs = json!({
+                        "bucket": bucket,
+                        "prefix": prefix,
+                    });
+                    if let Some(credentials) = credentials {
+                        gcs["credentials"] = json!(credentials);
+                    }
+                    json!({
+                        "gcs": gcs
+                    })
+                },
+            };
+            terraform.insert("backend".to_string(), backend);
+        }
+        if self.emit_required_providers {
+            terraform.insert("required_providers".to_string(), json!(required_providers));
+        }
+        for (k, v) in &self.terraform_settings {
+            terraform.insert(k.clone(), v.clone());
+        }
+        let mut out = BTreeMap::new();
+        out.insert("terraform", Value::Object(terraform));
         if !providers.is_empty() {
             out.insert("provider", json!(providers));
         }
@@ -248,18 +770,135 @@ impl Stack {
         if !resources.is_empty() {
             out.insert("resource", json!(resources));
         }
+        if !ephemerals.is_empty() {
+            out.insert("ephemeral", json!(ephemerals));
+        }
+        if !modules.is_empty() {
+            out.insert("module", json!(modules));
+        }
         if !outputs.is_empty() {
             out.insert("output", json!(outputs));
         }
-        REPLACE_EXPRS.with(|f| *f.borrow_mut() = None);
-        let res = serde_json::to_vec_pretty(&out).unwrap();
-        Ok(res)
+        let address_included = |address: &str| -> bool {
+            let (type_, tf_id) = address_component(address);
+            return filter(&type_, tf_id);
+        };
+        let moved =
+            self
+                .moved
+                .iter()
+                .filter(|(_, to)| address_included(to))
+                .map(|(from, to)| json!({
+                    "from": from,
+                    "to": to,
+                }))
+                .collect::<Vec<_>>();
+        if !moved.is_empty() {
+            out.insert("moved", Value::Array(moved));
+        }
+        let imports =
+            self
+                .imports
+                .iter()
+                .filter(|(to, _)| address_included(to))
+                .map(|(to, id)| json!({
+                    "to": to,
+                    "id": id,
+                }))
+                .collect::<Vec<_>>();
+        if !imports.is_empty() {
+            out.insert("import", Value::Array(imports));
+        }
+        drop(replace_exprs);
+        out
+            .serialize(&mut serde_json::Serializer::with_formatter(writer, formatter))
+            .map_err(|e| StackError::WriteError(e.into()))?;
+        Ok(())
+    }
+
+    /// Set a key in the top-level `terraform` block, like `cloud` or
+    /// `provider_meta`, in Terraform's json syntax. This replaces any value terrars
+    /// generates for the key (`backend`, `required_providers`).
+    pub fn set_terraform_setting(&mut self, k: impl ToString, v: impl Serialize) {
+        self.terraform_settings.insert(k.to_string(), serde_json::to_value(v).unwrap());
+    }
+
+    /// Whether to declare the providers used by the stack in `required_providers`.
+    /// Disable this when the config is combined with hand-written config that already
+    /// declares them, since Terraform doesn't allow declaring a provider twice.
+    /// Defaults to true.
+    pub fn set_emit_required_providers(&mut self, v: bool) {
+        self.emit_required_providers = v;
+    }
+
+    /// Store the state with this backend instead of in a local file at the
+    /// `state_path` passed to `serialize`. Changing the backend of a directory that
+    /// has already been initialized requires running `terraform init -migrate-state`
+    /// (or `-reconfigure`) there.
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.backend = Some(backend);
+    }
+
+    /// Use HCP Terraform (or Terraform Enterprise) for state and runs instead of the
+    /// local backend. Authentication uses the credentials from `terraform login` or
+    /// `TF_TOKEN_*` environment variables. To use a host other than
+    /// `app.terraform.io`, set `TF_CLOUD_HOSTNAME`.
+    pub fn set_cloud(&mut self, organization: &str, workspaces: CloudWorkspaces) {
+        self.set_terraform_setting("cloud", json!({
+            "organization": organization,
+            "workspaces": workspaces,
+        }));
+    }
+
+    /// Require a Terraform version matching this constraint (`required_version`,
+    /// ex: `>= 1.5, < 2.0`).
+    pub fn set_required_version(&mut self, constraint: impl ToString) {
+        self.set_terraform_setting("required_version", constraint.to_string());
+    }
+
+    /// Enable experimental Terraform language features (`experiments`). These are
+    /// only available in alpha releases of Terraform.
+    pub fn set_experiments(&mut self, experiments: &[&str]) {
+        self.set_terraform_setting("experiments", experiments);
+    }
+
+    /// Set the metadata a provider receives about this module (`provider_meta`), for
+    /// providers that support it. `provider` is the name in `required_providers`.
+    pub fn set_provider_meta(&mut self, provider: &str, v: impl Serialize) {
+        let meta =
+            self
+                .terraform_settings
+                .entry("provider_meta".to_string())
+                .or_insert_with(|| Value::Object(Default::default()));
+        meta[provider] = serde_json::to_value(v).unwrap();
     }
 
     pub fn add_provider(&mut self, v: Rc<dyn Provider>) {
         self.providers.push(v);
     }
 
+    /// Build one provider per region, returning the providers keyed by region. The
+    /// providers need distinct aliases, ex:
+    ///
+    /// ```ignore
+    /// let providers = stack.for_each_region(["us-east-1", "eu-west-1"], |stack, region| {
+    ///     BuildProviderAws {}.build(stack).set_alias(region).set_region(region)
+    /// });
+    /// ```
+    pub fn for_each_region<P>(
+        &mut self,
+        regions: impl IntoIterator<Item = impl ToString>,
+        mut build: impl FnMut(&mut Stack, &str) -> P,
+    ) -> HashMap<String, P> {
+        let mut out = HashMap::new();
+        for region in regions {
+            let region = region.to_string();
+            let provider = build(self, &region);
+            out.insert(region, provider);
+        }
+        out
+    }
+
     pub fn add_datasource(&mut self, v: Rc<dyn Datasource_>) {
         self.datasources.push(v);
     }
@@ -268,68 +907,618 @@ impl Stack {
         self.resources.push(v);
     }
 
+    pub fn add_ephemeral(&mut self, v: Rc<dyn Ephemeral_>) {
+        self.ephemerals.push(v);
+    }
+
+    /// Tell Terraform a resource or module now has a different address (a `moved`
+    /// block), so renaming its tf_id doesn't destroy and recreate it. Addresses are
+    /// like `aws_instance.web` - use `extract_ref` to get one from a handle (ex:
+    /// `stack.add_moved("aws_instance.old", web.extract_ref())`).
+    pub fn add_moved(&mut self, from: impl ToString, to: impl ToString) {
+        self.moved.push((from.to_string(), to.to_string()));
+    }
+
+    /// Import an existing resource into the state when the stack is applied (an
+    /// `import` block, Terraform 1.5 and newer). `id` is the provider-specific import
+    /// id. Unlike `import`, this shows up in the plan, and the block can stay in the
+    /// config afterwards.
+    pub fn add_import(&mut self, to: &impl Referable, id: impl ToString) {
+        self.imports.push((to.extract_ref(), id.to_string().into()));
+    }
+
+    pub fn add_module(&mut self, v: Rc<dyn Module>) {
+        self.modules.push(v);
+    }
+
+    pub fn add_variable(&mut self, v: Rc<dyn VariableTrait>) {
+        self.variables.push(v);
+    }
+
+    pub fn add_output(&mut self, v: Rc<dyn Output>) {
+        self.outputs.push(v);
+    }
+
+    /// Like `add_datasource` but fails if the tf_id isn't a valid Terraform
+    /// identifier or a datasource of the same type with the same tf_id was already
+    /// added (normally detected when serializing).
+    pub fn try_add_datasource(&mut self, v: Rc<dyn Datasource_>) -> Result<(), StackError> {
+        let (type_, id) = (v.extract_datasource_type(), v.extract_tf_id());
+        if !valid_tf_id(&id) {
+            return Err(StackError::InvalidId(ComponentType::Datasource, id));
+        }
+        if self.datasources.iter().any(|d| d.extract_datasource_type() == type_ && d.extract_tf_id() == id) {
+            return Err(StackError::Duplicate(ComponentType::Datasource, id));
+        }
+        self.add_datasource(v);
+        Ok(())
+    }
+
+    /// Like `add_resource` but checks the tf_id, like `try_add_datasource`.
+    pub fn try_add_resource(&mut self, v: Rc<dyn Resource_>) -> Result<(), StackError> {
+        let (type_, id) = (v.extract_resource_type(), v.extract_tf_id());
+        if !valid_tf_id(&id) {
+            return Err(StackError::InvalidId(ComponentType::Resource, id));
+        }
+        if self.resources.iter().any(|r| r.extract_resource_type() == type_ && r.extract_tf_id() == id) {
+            return Err(StackError::Duplicate(ComponentType::Resource, id));
+        }
+        self.add_resource(v);
+        Ok(())
+    }
+
+    /// Like `add_ephemeral` but checks the tf_id, like `try_add_datasource`.
+    pub fn try_add_ephemeral(&mut self, v: Rc<dyn Ephemeral_>) -> Result<(), StackError> {
+        let (type_, id) = (v.extract_ephemeral_type(), v.extract_tf_id());
+        if !valid_tf_id(&id) {
+            return Err(StackError::InvalidId(ComponentType::Ephemeral, id));
+        }
+        if self.ephemerals.iter().any(|e| e.extract_ephemeral_type() == type_ && e.extract_tf_id() == id) {
+            return Err(StackError::Duplicate(ComponentType::Ephemeral, id));
+        }
+        self.add_ephemeral(v);
+        Ok(())
+    }
+
+    /// The number of resources in the stack. Resources with `count` or `for_each`
+    /// are counted once.
+    pub fn resource_count(&self) -> usize {
+        self.resources.len()
+    }
+
+    pub fn datasource_count(&self) -> usize {
+        self.datasources.len()
+    }
+
+    pub fn ephemeral_count(&self) -> usize {
+        self.ephemerals.len()
+    }
+
+    pub fn module_count(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn provider_count(&self) -> usize {
+        self.providers.len()
+    }
+
+    pub fn variable_count(&self) -> usize {
+        self.variables.len()
+    }
+
+    pub fn output_count(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// The size in bytes of the output of `serialize`.
+    pub fn serialized_size(&self, state_path: &Path) -> Result<usize, StackError> {
+        Ok(self.serialize(state_path)?.len())
+    }
+
     /// Serialize the stack to a file and run a Terraform command on it. If variables
     /// are provided, they must be a single-level struct where all values are
     /// primitives (i64, f64, String, bool).
     pub fn run<V: Serialize>(&self, path: &Path, variables: Option<&V>, mode: &str) -> Result<(), RunError> {
-        create_dir_all(path).map_err(|e| RunError::FsError(path.to_path_buf(), e))?;
-        let state_name = "state.tfstate";
-        fs::write(&path.join("stack.tf.json"), &self.serialize(&PathBuf::from_str(state_name).unwrap())?)?;
-        let state_path = path.join(state_name);
-        if !state_path.exists() {
-            let mut command = Command::new(get_terraform_binary());
-            command.current_dir(&path).arg("init");
-            let res = command.status()?;
-            if !res.success() {
-                return Err(RunError::CommandError(command, res));
+        self.run_with_options(path, variables, mode, &RunOptions::default())?;
+        Ok(())
+    }
+
+    /// Like `run` but with additional settings. Returns the paths of the files
+    /// written in `path`.
+    pub fn run_with_options<V: Serialize>(
+        &self,
+        path: &Path,
+        variables: Option<&V>,
+        mode: &str,
+        options: &RunOptions,
+    ) -> Result<RunResult, RunError> {
+        self.prepare_run(path, options)?;
+        let mut command = terraform_command(path, options);
+        command.arg(mode);
+        if matches!(mode, "plan" | "apply" | "destroy" | "refresh") {
+            if !options.lock {
+                command.arg("-lock=false");
+            }
+            if let Some(timeout) = options.lock_timeout {
+                command.arg(format!("-lock-timeout={}ms", timeout.as_millis()));
+            }
+            for target in &options.targets {
+                command.arg(format!("-target={}", target));
             }
         }
-        let mut command = Command::new(get_terraform_binary());
-        command.current_dir(&path).arg(mode);
-        if let Some(vars) = variables {
-            let mut vars_file = tempfile::Builder::new().suffix(".json").tempfile()?;
-            vars_file.as_file_mut().write_all(&serde_json::to_vec_pretty(&vars)?)?;
-            command.arg(format!("-var-file={}", vars_file.path().to_string_lossy()));
-            let res = command.status()?;
-            if !res.success() {
-                return Err(RunError::CommandError(command, res))?;
+        if matches!(mode, "plan" | "apply") {
+            for address in &options.replace {
+                command.arg(format!("-replace={}", address));
             }
+        }
+        let _vars_file = add_vars_file(&mut command, variables, options)?;
+        run_command(command)?;
+        Ok(RunResult {
+            work_dir: path.to_path_buf(),
+            config_path: path.join(CONFIG_NAME),
+            state_path: self.local_state_path(path, options.workspace.as_deref()),
+        })
+    }
+
+    /// The state file for `workspace` when running in `path` with the local backend.
+    fn local_state_path(&self, path: &Path, workspace: Option<&str>) -> PathBuf {
+        let local_state = path.join(match &self.backend {
+            Some(Backend::Local { path }) => path.as_path(),
+            _ => Path::new(STATE_NAME),
+        });
+        return match workspace {
+            None | Some("default") => local_state,
+            // The local backend ignores the configured file name for other workspaces
+            Some(workspace) => local_state
+                .parent()
+                .unwrap()
+                .join("terraform.tfstate.d")
+                .join(workspace)
+                .join("terraform.tfstate"),
+        };
+    }
+
+    /// Like `run_with_options` but in a new, unique temporary directory so multiple
+    /// stacks can be run in parallel. The directory is deleted when the returned
+    /// handle is dropped unless `keep` is called - destroy any created resources
+    /// first (with `handle.path()`) since the state is deleted along with it.
+    ///
+    /// If the run fails the directory is kept, since some resources may have been
+    /// created, and the error is `RunError::TempDirRunError` with its path.
+    pub fn run_in_tempdir<V: Serialize>(
+        &self,
+        variables: Option<&V>,
+        mode: &str,
+        options: &RunOptions,
+    ) -> Result<TempApplyHandle, RunError> {
+        let mut builder = tempfile::Builder::new();
+        builder.prefix("terrars-");
+        let dir = match &options.temp_dir {
+            Some(temp_dir) => builder.tempdir_in(temp_dir)?,
+            None => builder.tempdir()?,
+        };
+        if let Err(e) = self.run_with_options(dir.path(), variables, mode, options) {
+            return Err(RunError::TempDirRunError(dir.keep(), Box::new(e)));
+        }
+        Ok(TempApplyHandle {
+            path: dir.path().to_path_buf(),
+            dir: Some(dir),
+        })
+    }
+
+    /// Writes the stack config to `path` and initializes Terraform there if it hasn't
+    /// been yet.
+    fn prepare_run(&self, path: &Path, options: &RunOptions) -> Result<(), RunError> {
+        create_dir_all(path).map_err(|e| RunError::FsError(path.to_path_buf(), e))?;
+        fs::write(path.join(CONFIG_NAME), &self.serialize(&PathBuf::from_str(STATE_NAME).unwrap())?)?;
+        self.check_module_sources(path)?;
+        if let Some(cache_dir) = &options.plugin_cache_dir {
+            create_dir_all(cache_dir).map_err(|e| RunError::FsError(cache_dir.clone(), e))?;
+        }
+        if let Some(mirror) = &options.provider_mirror {
+            fs::write(
+                path.join(CLI_CONFIG_NAME),
+                format!(
+                    "provider_installation {{\n  filesystem_mirror {{\n    path = {}\n  }}\n}}\n",
+                    serde_json::to_string(&absolute_path(mirror)).unwrap()
+                ),
+            )?;
+        }
+        let initialized = if self.terraform_settings.contains_key("cloud") || self.backend.is_some() {
+            // The state may not be local, but init records the backend settings here
+            path.join(".terraform").join("terraform.tfstate").exists()
         } else {
-            let res = command.status()?;
-            if !res.success() {
-                return Err(RunError::CommandError(command, res))?;
+            self.local_state_path(path, options.workspace.as_deref()).exists()
+        };
+        if !initialized {
+            run_terraform(path, options, &["init"])?;
+        }
+        if let Some(workspace) = &options.workspace {
+            run_terraform(path, options, &["workspace", "select", "-or-create", workspace])?;
+        }
+        self.check_provider_versions(path, options)?;
+        Ok(())
+    }
+
+    /// Imports an existing resource into the state (`terraform import`). The stack
+    /// config is written and initialized first so that `address` exists. `id` is the
+    /// provider-specific import id. Variables are as for `run`.
+    pub fn import<V: Serialize>(
+        &self,
+        path: &Path,
+        variables: Option<&V>,
+        address: &impl Referable,
+        id: &str,
+    ) -> Result<(), RunError> {
+        let options = RunOptions::default();
+        self.prepare_run(path, &options)?;
+        let mut command = terraform_command(path, &options);
+        command.arg("import");
+        let _vars_file = add_vars_file(&mut command, variables, &options)?;
+        command.arg(address.extract_ref()).arg(id);
+        run_command(command)
+    }
+
+    /// Terraform resolves local module sources (`./` or `../`) relative to the
+    /// directory containing the config, so fail early if one doesn't exist there
+    /// rather than during `init`.
+    fn check_module_sources(&self, path: &Path) -> Result<(), RunError> {
+        for m in &self.modules {
+            let value = m.extract_value();
+            let source = match value.get("source").and_then(Value::as_str) {
+                Some(s) => s,
+                None => continue,
+            };
+            if !source.starts_with("./") && !source.starts_with("../") {
+                continue;
+            }
+            let resolved = path.join(source);
+            if !resolved.exists() {
+                return Err(RunError::ModuleSourceMissing(m.extract_tf_id(), resolved));
             }
         }
         Ok(())
     }
 
+    fn check_provider_versions(&self, path: &Path, options: &RunOptions) -> Result<(), RunError> {
+        let check = options.provider_version_check;
+        if check == ProviderVersionCheck::Off {
+            return Ok(());
+        }
+        let lock_path = path.join(".terraform.lock.hcl");
+        if !lock_path.exists() {
+            return Ok(());
+        }
+        let locked = parse_lock_versions(&fs::read_to_string(&lock_path)?);
+        let mut expected = BTreeMap::new();
+        for p in &self.providers {
+            let provider_type = p.extract_provider_type();
+            let version =
+                p
+                    .extract_schema_version()
+                    .or_else(|| provider_type.get("version").and_then(Value::as_str).map(ToString::to_string));
+            if let (Some(source), Some(version)) = (provider_type.get("source").and_then(Value::as_str), version) {
+                expected.insert(source.to_string(), version);
+            }
+        }
+        for (source, version) in expected {
+            // Only exact versions can be compared, skip constraints
+            let version = version.trim().trim_start_matches('=').trim();
+            if version.is_empty() ||
+                !version.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '+') {
+                continue;
+            }
+            let key = if source.split('/').count() == 3 {
+                source.to_lowercase()
+            } else {
+                format!("registry.terraform.io/{}", source.to_lowercase())
+            };
+            let found = match locked.get(&key) {
+                Some(f) => f,
+                None => continue,
+            };
+            if found != version {
+                match check {
+                    ProviderVersionCheck::Off => { },
+                    ProviderVersionCheck::Warn => match &options.provider_version_warning {
+                        Some(warning) => (warning.0)(&source, version, found),
+                        None => eprintln!(
+                            "Warning: provider {} was generated for version {} but version {} is installed",
+                            source,
+                            version,
+                            found
+                        ),
+                    },
+                    ProviderVersionCheck::Error => {
+                        return Err(RunError::ProviderVersionMismatch(source, version.to_string(), found.clone()));
+                    },
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the stack config to `path` and records the provider checksums for each
+    /// platform in the lock file (`terraform providers lock`), so the committed lock
+    /// file works on other OSes and architectures. Platforms are like
+    /// `linux_amd64`, `darwin_arm64`, `windows_amd64`.
+    pub fn providers_lock(&self, path: &Path, platforms: &[&str]) -> Result<(), RunError> {
+        let options = RunOptions::default();
+        self.prepare_run(path, &options)?;
+        let mut command = terraform_command(path, &options);
+        command.args(["providers", "lock"]);
+        for platform in platforms {
+            command.arg(format!("-platform={}", platform));
+        }
+        run_command(command)
+    }
+
+    /// Plans the stack (`terraform plan -detailed-exitcode`) and returns whether
+    /// applying it would change anything, for detecting drift. Variables are as for
+    /// `run`.
+    pub fn plan_has_changes<V: Serialize>(&self, path: &Path, variables: Option<&V>) -> Result<bool, RunError> {
+        let options = RunOptions::default();
+        self.prepare_run(path, &options)?;
+        let mut command = terraform_command(path, &options);
+        command.arg("plan").arg("-detailed-exitcode");
+        let _vars_file = add_vars_file(&mut command, variables, &options)?;
+        match run_command_stdout(command, Stdio::inherit()) {
+            Ok(_) => Ok(false),
+            // Exit code 2 means the plan succeeded and has changes, anything else
+            // non-zero is an error
+            Err(RunError::CommandError(CommandFailure { code: Some(2), .. })) => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Moves a resource to a new address in the state (`terraform state mv`), for
+    /// instance after renaming it. `path` is the directory in which the stack was run.
+    pub fn state_mv(&self, path: &Path, from: &str, to: &str) -> Result<(), RunError> {
+        run_terraform(path, &RunOptions::default(), &["state", "mv", from, to])
+    }
+
+    /// Removes a resource from the state without destroying it (`terraform state
+    /// rm`). `path` is the directory in which the stack was run.
+    pub fn state_rm(&self, path: &Path, address: &str) -> Result<(), RunError> {
+        run_terraform(path, &RunOptions::default(), &["state", "rm", address])
+    }
+
     /// Gets the current outputs from an applied stack. `path` is the directory in
     /// which the .tf.json file was written. The output struct must be a single level
     /// and only have primitive values (i64, f64, String, bool).
     pub fn get_output<O: DeserializeOwned>(&self, path: &Path) -> Result<O, RunError> {
-        let mut command = Command::new(get_terraform_binary());
-        let res = command.current_dir(&path).stderr(Stdio::inherit()).args(&["output", "-json"]).output()?;
-        if !res.status.success() {
-            return Err(RunError::CommandError(command, res.status));
-        }
-
-        // Redeserialize... hack
-        #[derive(Deserialize)]
-        struct Var {
-            value: Value,
-        }
-
-        Ok(
-            serde_json::from_slice(
-                &serde_json::to_vec(
-                    &serde_json::from_slice::<HashMap<String, Var>>(&res.stdout)?
-                        .into_iter()
-                        .map(|(k, v)| (k, v.value))
-                        .collect::<HashMap<String, Value>>(),
-                )?,
+        read_outputs(path)
+    }
+}
+
+/// A temporary directory a stack was run in, returned by `Stack::run_in_tempdir`.
+pub struct TempApplyHandle {
+    path: PathBuf,
+    dir: Option<tempfile::TempDir>,
+}
+
+impl TempApplyHandle {
+    /// The directory the stack was run in.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the current outputs, see `Stack::get_output`.
+    pub fn get_output<O: DeserializeOwned>(&self) -> Result<O, RunError> {
+        read_outputs(&self.path)
+    }
+
+    /// Don't delete the directory when the handle is dropped.
+    pub fn keep(&mut self) {
+        if let Some(dir) = self.dir.take() {
+            // The path is already stored in the handle
+            let _ = dir.keep();
+        }
+    }
+}
+
+fn read_outputs<O: DeserializeOwned>(path: &Path) -> Result<O, RunError> {
+    let mut command = Command::new(get_terraform_binary());
+    command.current_dir(path).args(["output", "-json"]);
+    let stdout = run_command_stdout(command, Stdio::piped())?;
+
+    // Redeserialize... hack
+    #[derive(Deserialize)]
+    struct Var {
+        value: Value,
+    }
+
+    Ok(
+        serde_json::from_slice(
+            &serde_json::to_vec(
+                &serde_json::from_slice::<HashMap<String, Var>>(&stdout)?
+                    .into_iter()
+                    .map(|(k, v)| (k, v.value))
+                    .collect::<HashMap<String, Value>>(),
             )?,
-        )
+        )?,
+    )
+}
+
+/// The type and tf_id of the component an address (ex: `aws_instance.x["a"]`,
+/// `data.aws_ami.x`, `module.x.aws_instance.y`) refers to, for filtering.
+fn address_component(address: &str) -> (ComponentType, &str) {
+    /// The first segment of `rest`, without any instance key.
+    fn first_segment(rest: &str) -> &str {
+        return &rest[..rest.find(['.', '[']).unwrap_or(rest.len())];
+    }
+
+    if let Some(rest) = address.strip_prefix("module.") {
+        return (ComponentType::Module, first_segment(rest));
+    }
+    let (type_, rest) = match address.strip_prefix("data.") {
+        Some(rest) => (ComponentType::Datasource, rest),
+        None => (ComponentType::Resource, address),
+    };
+    let rest = rest.split_once('.').map(|(_, rest)| rest).unwrap_or(rest);
+    return (type_, first_segment(rest));
+}
+
+fn local_backend(state_path: &Path) -> Value {
+    let mut backend = json!({
+        "path": state_path.to_string_lossy(),
+    });
+    match state_path.parent() {
+        // Keep non-default workspaces' state next to the default state rather than in
+        // the working directory. This is only set when needed since changing the
+        // backend config requires reinitializing.
+        Some(parent) if !parent.as_os_str().is_empty() => {
+            backend["workspace_dir"] = json!(parent.join("terraform.tfstate.d").to_string_lossy());
+        },
+        _ => { },
+    }
+    return json!({
+        "local": backend
+    });
+}
+
+/// Escape literal text for use inside a quoted Terraform template string.
+pub(crate) fn escape_template_literal(s: &str) -> String {
+    s
+        .replace("\\", "\\\\")
+        .replace("\"", "\\\"")
+        .replace("\n", "\\n")
+        .replace("\r", "\\r")
+        .replace("${", "$${")
+        .replace("%{", "%%{")
+}
+
+/// Terraform runs in the stack directory, so make relative paths absolute.
+fn absolute_path(p: &Path) -> PathBuf {
+    std::env::current_dir().map(|d| d.join(p)).unwrap_or_else(|_| p.to_path_buf())
+}
+
+fn terraform_command(path: &Path, options: &RunOptions) -> Command {
+    let mut command = Command::new(get_terraform_binary());
+    command.current_dir(path);
+    if let Some(cache_dir) = &options.plugin_cache_dir {
+        command.env("TF_PLUGIN_CACHE_DIR", absolute_path(cache_dir));
+    }
+    if options.provider_mirror.is_some() {
+        command.env("TF_CLI_CONFIG_FILE", absolute_path(&path.join(CLI_CONFIG_NAME)));
+    }
+    command
+}
+
+/// Whether `id` is a valid Terraform identifier, for use as a block name.
+fn valid_tf_id(id: &str) -> bool {
+    let mut chars = id.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => { },
+        _ => return false,
+    }
+    chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Runs a command, failing if it exits unsuccessfully.
+fn run_command(command: Command) -> Result<(), RunError> {
+    run_command_stdout(command, Stdio::inherit())?;
+    Ok(())
+}
+
+/// Runs a command, failing if it exits unsuccessfully. Stderr is passed through as
+/// it's written and also captured for the error. Returns stdout if `stdout` is
+/// `Stdio::piped()`.
+fn run_command_stdout(mut command: Command, stdout: Stdio) -> Result<Vec<u8>, RunError> {
+    let mut child = command.stdout(stdout).stderr(Stdio::piped()).spawn()?;
+    let mut child_stderr = child.stderr.take().unwrap();
+    let stderr_thread = std::thread::spawn(move || {
+        let mut captured = vec![];
+        let mut buf = [0u8; 4096];
+        loop {
+            match child_stderr.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    let _ = io::stderr().write_all(&buf[..n]);
+                    captured.extend_from_slice(&buf[..n]);
+                },
+            }
+        }
+        captured
+    });
+    let mut out = vec![];
+    if let Some(mut child_stdout) = child.stdout.take() {
+        child_stdout.read_to_end(&mut out)?;
+    }
+    let status = child.wait()?;
+    let stderr = stderr_thread.join().unwrap_or_default();
+    if !status.success() {
+        return Err(RunError::CommandError(CommandFailure::new(&command, status, &stderr)));
+    }
+    Ok(out)
+}
+
+/// Removes terminal color/formatting escape sequences.
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip `ESC [ params letter`, leaving whatever follows a lone `ESC`
+            if chars.next_if_eq(&'[').is_some() {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Runs a Terraform command in `path`, failing if it exits unsuccessfully.
+fn run_terraform(path: &Path, options: &RunOptions, args: &[&str]) -> Result<(), RunError> {
+    let mut command = terraform_command(path, options);
+    command.args(args);
+    run_command(command)
+}
+
+/// Writes the variables to a temporary file and passes it to the command with
+/// `-var-file`. The file is deleted when the returned value is dropped.
+fn add_vars_file<V: Serialize>(
+    command: &mut Command,
+    variables: Option<&V>,
+    options: &RunOptions,
+) -> Result<Option<tempfile::NamedTempFile>, RunError> {
+    let vars = match variables {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+    let mut builder = tempfile::Builder::new();
+    builder.suffix(".json");
+    let mut vars_file = match &options.temp_dir {
+        Some(dir) => builder.tempfile_in(dir)?,
+        None => builder.tempfile()?,
+    };
+    vars_file.as_file_mut().write_all(&serde_json::to_vec_pretty(&vars)?)?;
+    command.arg(format!("-var-file={}", vars_file.path().to_string_lossy()));
+    Ok(Some(vars_file))
+}
+
+/// Extracts the provider versions from a `.terraform.lock.hcl` file, keyed by the
+/// full provider address (ex: `registry.terraform.io/hashicorp/aws`).
+fn parse_lock_versions(lock: &str) -> BTreeMap<String, String> {
+    let mut out = BTreeMap::new();
+    let mut provider = None;
+    for line in lock.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("provider ") {
+            provider = rest.trim().trim_end_matches('{').trim().strip_prefix('"').and_then(|r| r.strip_suffix('"'));
+        } else if let Some(rest) = line.strip_prefix("version") {
+            if let (Some(p), Some(v)) = (provider, rest.trim().strip_prefix('=')) {
+                out.insert(p.to_string(), v.trim().trim_matches('"').to_string());
+            }
+        }
     }
+    out
 }
 
 // Generated traits
@@ -341,6 +1530,11 @@ pub trait Provider {
     fn extract_type_tf_id(&self) -> String;
     fn extract_provider_type(&self) -> Value;
     fn extract_provider(&self) -> Value;
+
+    /// The provider version the bindings were generated for, if known.
+    fn extract_schema_version(&self) -> Option<String> {
+        None
+    }
 }
 
 pub trait Datasource: Referable { }
@@ -359,6 +1553,16 @@ pub trait Resource_ {
     fn extract_value(&self) -> Value;
 }
 
+/// Ephemeral resources (Terraform 1.10+) aren't stored in the state, and are
+/// referred to as `ephemeral.<type>.<name>`.
+pub trait Ephemeral: Referable { }
+
+pub trait Ephemeral_ {
+    fn extract_ephemeral_type(&self) -> String;
+    fn extract_tf_id(&self) -> String;
+    fn extract_value(&self) -> Value;
+}
+
 // Provider extras
 #[derive(Serialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -379,7 +1583,11 @@ pub struct ResourceLifecycle {
     pub prevent_destroy: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ignore_changes: Option<IgnoreChanges>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub replace_triggered_by: Vec<String>,
+    /// Extra `lifecycle` arguments, merged over the typed ones when serializing
+    #[serde(flatten)]
+    pub raw: serde_json::Map<String, Value>,
 }
 
 #[derive(Serialize)]
@@ -389,11 +1597,34 @@ pub struct DynamicBlock<T: Serialize> {
     pub content: T,
 }
 
+impl<T: Serialize> DynamicBlock<T> {
+    /// Create a dynamic block with an element for each element of `collection`.
+    /// `inner` receives the iterator, which can be used to refer to the key and value
+    /// of the current element.
+    pub fn over<C: CollectionRef>(collection: &C, inner: impl FnOnce(MapKV<C::Element>) -> T) -> Self {
+        let (shared, base) = collection.collection_base();
+        DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: inner(MapKV::new(shared)),
+        }
+    }
+}
+
+/// The value for a block field: a list of literal block elements, or a dynamic
+/// block. A `Vec` of elements, a single element, or a `DynamicBlock` can all be
+/// converted with `into()`.
 pub enum BlockAssignable<T: Serialize> {
     Literal(Vec<T>),
     Dynamic(DynamicBlock<T>),
 }
 
+impl<T: Serialize> From<T> for BlockAssignable<T> {
+    fn from(value: T) -> Self {
+        BlockAssignable::Literal(vec![value])
+    }
+}
+
 impl<T: Serialize> From<Vec<T>> for BlockAssignable<T> {
     fn from(value: Vec<T>) -> Self {
         BlockAssignable::Literal(value)
@@ -442,3 +1673,332 @@ impl<T: Ref> MapKV<T> {
         T::new(self.shared.clone(), "each.value".into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::Path,
+        rc::Rc,
+    };
+    use serde_json::{
+        json,
+        Value,
+    };
+    use crate::{
+        AnyField,
+        BuildModule,
+        BuildStack,
+        ComponentType,
+        PrimExpr,
+        ProviderVersionCheck,
+        ProviderVersionWarning,
+        RecRef,
+        Referable,
+        Resource_,
+        RunError,
+        RunOptions,
+    };
+
+    #[test]
+    fn format_id_placeholders() {
+        let stack = BuildStack {}.build();
+        let bucket = || -> PrimExpr<String> {
+            stack.expr("var.bucket")
+        };
+        assert_eq!(
+            stack
+                .format_id("arn:aws:s3:{region}:::{bucket}/*", &[("bucket", bucket()), ("region", stack.expr("var.region"))])
+                .raw(),
+            "\"arn:aws:s3:${var.region}:::${var.bucket}/*\""
+        );
+        assert_eq!(
+            stack.format_id("{bucket}-{bucket}", &[("bucket", bucket())]).raw(),
+            "\"${var.bucket}-${var.bucket}\""
+        );
+    }
+
+    #[test]
+    fn format_id_escapes_literals() {
+        let stack = BuildStack {}.build();
+        assert_eq!(
+            stack.format_id("{{x}} \"${{y}}\" %{{z}}", &[]).raw(),
+            "\"{x} \\\"$${y}\\\" %%{z}\""
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Missing part for placeholder [name]")]
+    fn format_id_unknown_placeholder() {
+        let stack = BuildStack {}.build();
+        stack.format_id("x-{name}", &[("other", stack.expr("var.other"))]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unterminated placeholder")]
+    fn format_id_unterminated_placeholder() {
+        let stack = BuildStack {}.build();
+        stack.format_id("x-{name", &[]);
+    }
+
+    #[test]
+    fn each_and_count() {
+        let stack = BuildStack {}.build();
+        assert_eq!(stack.each_key().raw(), "each.key");
+        assert_eq!(stack.each_value::<PrimExpr<String>>().raw(), "each.value");
+        assert_eq!(stack.each_value::<RecRef<PrimExpr<i64>>>().get("port").raw(), "each.value[\"port\"]");
+        assert_eq!(stack.count_index().raw(), "count.index");
+    }
+
+    #[test]
+    fn write_to() {
+        let stack = BuildStack {}.build();
+        let mut out = vec![];
+        stack.write_to(&mut out, Path::new("state.tfstate")).unwrap();
+        assert_eq!(out, stack.serialize(Path::new("state.tfstate")).unwrap());
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed["terraform"]["backend"]["local"]["path"], "state.tfstate");
+    }
+
+    #[test]
+    fn module_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = dir.path().join("run");
+        let mut stack = BuildStack {}.build();
+        BuildModule {
+            tf_id: "registry".into(),
+            source: "terraform-aws-modules/vpc/aws".into(),
+        }.build(&mut stack);
+        BuildModule {
+            tf_id: "vpc".into(),
+            source: "./modules/vpc".into(),
+        }.build(&mut stack);
+        BuildModule {
+            tf_id: "shared".into(),
+            source: "../shared".into(),
+        }.build(&mut stack);
+        match stack.check_module_sources(&run_dir) {
+            Err(RunError::ModuleSourceMissing(id, path)) => {
+                assert_eq!(id, "vpc");
+                assert_eq!(path, run_dir.join("./modules/vpc"));
+            },
+            _ => panic!("Missing local module source wasn't reported"),
+        }
+        std::fs::create_dir_all(run_dir.join("modules/vpc")).unwrap();
+        match stack.check_module_sources(&run_dir) {
+            Err(RunError::ModuleSourceMissing(id, _)) => assert_eq!(id, "shared"),
+            _ => panic!("Missing parent module source wasn't reported"),
+        }
+        std::fs::create_dir_all(dir.path().join("shared")).unwrap();
+        stack.check_module_sources(&run_dir).unwrap();
+    }
+
+    #[test]
+    fn run_in_tempdir_keeps_failed_dir() {
+        let parent = tempfile::tempdir().unwrap();
+        let mut stack = BuildStack {}.build();
+        BuildModule {
+            tf_id: "vpc".into(),
+            source: "./modules/vpc".into(),
+        }.build(&mut stack);
+        let options = RunOptions {
+            temp_dir: Some(parent.path().to_path_buf()),
+            ..Default::default()
+        };
+        match stack.run_in_tempdir::<()>(None, "apply", &options) {
+            Err(RunError::TempDirRunError(path, e)) => {
+                assert!(matches!(*e, RunError::ModuleSourceMissing(..)));
+                assert_eq!(path.parent(), Some(parent.path()));
+                assert!(path.join("stack.tf.json").exists());
+            },
+            _ => panic!("Failed run wasn't reported with its directory"),
+        }
+    }
+
+    struct FakeProvider;
+
+    impl crate::Provider for FakeProvider {
+        fn extract_type_tf_id(&self) -> String {
+            return "fake".to_string();
+        }
+
+        fn extract_provider_type(&self) -> Value {
+            return json!({
+                "source": "hashicorp/fake",
+                "version": "1.0.0"
+            });
+        }
+
+        fn extract_provider(&self) -> Value {
+            return json!({});
+        }
+    }
+
+    #[test]
+    fn provider_version_warning() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".terraform.lock.hcl"),
+            "provider \"registry.terraform.io/hashicorp/fake\" {\n  version = \"1.2.0\"\n}\n",
+        ).unwrap();
+        let mut stack = BuildStack {}.build();
+        stack.add_provider(Rc::new(FakeProvider));
+        let found = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let options = RunOptions {
+            provider_version_check: ProviderVersionCheck::Warn,
+            provider_version_warning: Some(ProviderVersionWarning::new({
+                let found = found.clone();
+                move |source, generated, installed| {
+                    found.lock().unwrap().push((source.to_string(), generated.to_string(), installed.to_string()))
+                }
+            })),
+            ..Default::default()
+        };
+        stack.check_provider_versions(dir.path(), &options).unwrap();
+        assert_eq!(
+            *found.lock().unwrap(),
+            vec![("hashicorp/fake".to_string(), "1.0.0".to_string(), "1.2.0".to_string())]
+        );
+        let options = RunOptions {
+            provider_version_check: ProviderVersionCheck::Error,
+            ..options
+        };
+        assert!(matches!(stack.check_provider_versions(dir.path(), &options), Err(RunError::ProviderVersionMismatch(..))));
+    }
+
+    /// A resource with a single `value` attribute, serialized when the stack is.
+    struct Input {
+        value: AnyField,
+    }
+
+    impl Resource_ for Input {
+        fn extract_resource_type(&self) -> String {
+            return "terraform_data".to_string();
+        }
+
+        fn extract_tf_id(&self) -> String {
+            return "x".to_string();
+        }
+
+        fn extract_value(&self) -> Value {
+            return json!({
+                "input": serde_json::to_value(&self.value).unwrap()
+            });
+        }
+    }
+
+    /// Serialize `expr` as an attribute value.
+    fn serialize_expr(expr: &str) -> Value {
+        let mut stack = BuildStack {}.build();
+        let value = AnyField::Sentinel(stack.shared.add_sentinel(expr));
+        stack.add_resource(Rc::new(Input { value: value }));
+        let out: Value = serde_json::from_slice(&stack.serialize(Path::new("state.tfstate")).unwrap()).unwrap();
+        return out["resource"]["terraform_data"]["x"]["input"].clone();
+    }
+
+    #[test]
+    fn failed_serialize_clears_replacements() {
+        let mut stack = BuildStack {}.build();
+        stack.add_resource(Rc::new(Input { value: AnyField::Literal(json!("a")) }));
+        stack.add_resource(Rc::new(Input { value: AnyField::Literal(json!("b")) }));
+        assert!(stack.serialize(Path::new("state.tfstate")).is_err());
+        assert!(crate::utils::REPLACE_EXPRS.with(|f| f.borrow().is_none()));
+    }
+
+    #[test]
+    fn expr_lit_escapes_template_sequences() {
+        let stack = BuildStack {}.build();
+        let lit = stack.expr_lit("a\\b \"${c}\" %{d}\r\n".to_string());
+        assert_eq!(lit.raw(), "\"a\\\\b \\\"$${c}\\\" %%{d}\\r\\n\"");
+        assert_eq!(serialize_expr(&lit.raw()), json!("${\"a\\\\b \\\"$${c}\\\" %%{d}\\r\\n\"}"));
+
+        // Lone `$`, `%` and braces aren't template sequences
+        assert_eq!(stack.expr_lit("$a %b {c}".to_string()).raw(), "\"$a %b {c}\"");
+    }
+
+    #[test]
+    fn object() {
+        let stack = BuildStack {}.build();
+        let obj = stack.object(vec![("a", stack.expr::<String>("var.a")), ("b\"${c}", stack.expr_lit("d\"${e}".to_string()))]);
+        assert_eq!(obj.base, "{\"a\" = var.a, \"b\\\"$${c}\" = \"d\\\"$${e}\"}");
+        assert_eq!(obj.get("a").raw(), "{\"a\" = var.a, \"b\\\"$${c}\" = \"d\\\"$${e}\"}[\"a\"]");
+        assert_eq!(serialize_expr(&obj.base), json!("${{\"a\" = var.a, \"b\\\"$${c}\" = \"d\\\"$${e}\"}}"));
+        assert_eq!(stack.object::<String>(Vec::<(String, _)>::new()).base, "{}");
+    }
+
+    #[test]
+    fn tuple() {
+        let stack = BuildStack {}.build();
+        let tuple = stack.tuple(vec![stack.expr::<String>("var.a"), stack.expr_lit("b\"${c}".to_string())]);
+        assert_eq!(tuple.base, "[var.a, \"b\\\"$${c}\"]");
+        assert_eq!(serialize_expr(&tuple.base), json!("${[var.a, \"b\\\"$${c}\"]}"));
+        assert_eq!(stack.tuple::<i64>(vec![]).base, "[]");
+    }
+
+    #[test]
+    fn strip_ansi() {
+        assert_eq!(super::strip_ansi("\x1b[31;1mError:\x1b[0m x"), "Error: x");
+        assert_eq!(super::strip_ansi("a\x1bb"), "ab");
+        assert_eq!(super::strip_ansi("a\x1b"), "a");
+        assert_eq!(super::strip_ansi("\x1b[1"), "");
+        assert_eq!(super::strip_ansi("é\x1b[2Kü"), "éü");
+    }
+
+    #[test]
+    fn address_component() {
+        let check = |address: &str, type_: ComponentType, tf_id: &str| {
+            let (got_type, got_tf_id) = super::address_component(address);
+            assert_eq!((format!("{:?}", got_type), got_tf_id), (format!("{:?}", type_), tf_id), "{}", address);
+        };
+        check("aws_instance.web", ComponentType::Resource, "web");
+        check("aws_instance.web[\"a.b\"]", ComponentType::Resource, "web");
+        check("aws_instance.web[0]", ComponentType::Resource, "web");
+        check("data.aws_ami.ubuntu", ComponentType::Datasource, "ubuntu");
+        check("module.vpc", ComponentType::Module, "vpc");
+        check("module.vpc[\"x\"].aws_subnet.a", ComponentType::Module, "vpc");
+        check("module.vpc.aws_subnet.a", ComponentType::Module, "vpc");
+    }
+
+    struct Address(&'static str);
+
+    impl Referable for Address {
+        fn extract_ref(&self) -> String {
+            return self.0.to_string();
+        }
+    }
+
+    #[test]
+    fn serialize_filtered_moved_import() {
+        let mut stack = BuildStack {}.build();
+        stack.add_resource(Rc::new(Input { value: AnyField::default() }));
+        stack.add_moved("terraform_data.old", "terraform_data.x");
+        stack.add_moved("terraform_data.old2[0]", "terraform_data.y[0]");
+        stack.add_moved("module.old", "module.net");
+        stack.add_moved("data.old.a", "data.old.b");
+        stack.add_import(&Address("terraform_data.x[\"k\"]"), "id1");
+        stack.add_import(&Address("terraform_data.y"), "id2");
+        let filtered = |filter: &dyn Fn(&ComponentType, &str) -> bool| -> Value {
+            return serde_json::from_slice(&stack.serialize_filtered(Path::new("state.tfstate"), filter).unwrap()).unwrap();
+        };
+        let out = filtered(&|type_, tf_id| matches!(type_, ComponentType::Resource) && tf_id == "x");
+        assert_eq!(out["moved"], json!([{
+            "from": "terraform_data.old",
+            "to": "terraform_data.x"
+        }]));
+        assert_eq!(out["import"], json!([{
+            "to": "terraform_data.x[\"k\"]",
+            "id": "id1"
+        }]));
+        let out = filtered(&|type_, tf_id| matches!(type_, ComponentType::Module) && tf_id == "net");
+        assert_eq!(out["moved"], json!([{
+            "from": "module.old",
+            "to": "module.net"
+        }]));
+        assert_eq!(out.get("import"), None);
+        let out = filtered(&|_, _| false);
+        assert_eq!(out.get("moved"), None);
+        assert_eq!(out.get("import"), None);
+        let out = filtered(&|_, _| true);
+        assert_eq!(out["moved"].as_array().unwrap().len(), 4);
+        assert_eq!(out["import"].as_array().unwrap().len(), 2);
+    }
+}
@@ -9,6 +9,9 @@ use crate::{
     },
     MapKV,
     Ref,
+    CollectionRef,
+    Expr,
+    ToFuncArg,
 };
 
 pub trait ToListMappable {
@@ -39,16 +42,50 @@ impl<T> Ref for ListRef<T> {
     }
 }
 
+impl<T: Ref> CollectionRef for ListRef<T> {
+    type Element = T;
+
+    fn collection_base(&self) -> (StackShared, String) {
+        (self.shared.clone(), self.base.clone())
+    }
+}
+
 impl<T: Ref> ListRef<T> {
+    /// Refer to the element at `index` (`list[index]`). Terraform fails if the
+    /// index is out of range; see `try_get` and `element` for alternatives.
     pub fn get(&self, index: usize) -> T {
         T::new(self.shared.clone(), format!("{}[{}]", &self.base, index))
     }
 
+    /// Like `get` but `null` if the index is out of range (`try(list[index],
+    /// null)`).
+    pub fn try_get(&self, index: usize) -> T {
+        T::new(self.shared.clone(), format!("try({}[{}], null)", &self.base, index))
+    }
+
+    /// Refer to an element with Terraform's `element` function, which wraps around
+    /// (the index is taken modulo the list length) instead of failing when it's out of
+    /// range. Fails if the list is empty or `index` is negative.
+    pub fn element(&self, index: impl ToFuncArg<i64>) -> T {
+        let (_, index) = index.to_func_arg(&self.shared).expr_raw();
+        T::new(self.shared.clone(), format!("element({}, {})", &self.base, index))
+    }
+
     pub fn map<O: ToListMappable>(&self, inner: impl FnOnce(MapKV<T>) -> O) -> O::O {
         let out = inner(MapKV::new(self.shared.clone()));
         out.do_map(self.base.clone())
     }
 
+    /// Like `map` but converts the list to a set first (`toset`). Use this when
+    /// creating a resource per element: Terraform only allows sets and maps in
+    /// resource `for_each`, so `map` on a list fails at plan time. The elements must
+    /// be strings and duplicates are merged. `each.key` and `each.value` are both the
+    /// element.
+    pub fn map_toset<O: ToListMappable>(&self, inner: impl FnOnce(MapKV<T>) -> O) -> O::O {
+        let out = inner(MapKV::new(self.shared.clone()));
+        out.do_map(format!("toset({})", self.base))
+    }
+
     pub fn map_rec<O: ListToRecMappable>(&self, inner: impl FnOnce(MapKV<T>) -> (PrimExpr<String>, O)) -> O::O {
         let (k, out) = inner(MapKV::new(self.shared.clone()));
         out.do_map_rec(self.base.clone(), k)
@@ -99,3 +136,24 @@ impl<T> MapListRefToRec<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BuildStack,
+        PrimExpr,
+        Ref,
+    };
+    use super::ListRef;
+
+    #[test]
+    fn try_get_and_element() {
+        let stack = BuildStack {}.build();
+        let names = ListRef::<PrimExpr<String>>::new(stack.shared.clone(), "var.names".to_string());
+        assert_eq!(names.try_get(2).raw(), "try(var.names[2], null)");
+        assert_eq!(names.element(5).raw(), "element(var.names, 5)");
+        assert_eq!(names.element(stack.count_index()).raw(), "element(var.names, count.index)");
+        let nested = ListRef::<ListRef<PrimExpr<i64>>>::new(stack.shared.clone(), "var.nested".to_string());
+        assert_eq!(nested.try_get(0).element(1).raw(), "element(try(var.nested[0], null), 1)");
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    cell::{
+        RefCell,
+    },
+    collections::BTreeMap,
+    rc::Rc,
+};
+use serde::{
+    Serialize,
+};
+use serde_json::{
+    Value,
+};
+use crate::{
+    AnyField,
+    ModuleRef,
+    Referable,
+    Stack,
+    StackShared,
+};
+
+pub trait Module {
+    fn extract_tf_id(&self) -> String;
+    fn extract_value(&self) -> Value;
+}
+
+#[derive(Serialize)]
+struct ModuleCallData {
+    source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    providers: BTreeMap<String, String>,
+    #[serde(flatten)]
+    inputs: BTreeMap<String, AnyField>,
+}
+
+struct ModuleCall_ {
+    shared: StackShared,
+    tf_id: String,
+    data: RefCell<ModuleCallData>,
+}
+
+/// A Terraform module call (a `module` block). Module inputs and outputs aren't
+/// typed, so set inputs with `set_input` and refer to outputs through `module_ref`.
+#[derive(Clone)]
+pub struct ModuleCall(Rc<ModuleCall_>);
+
+impl ModuleCall {
+    /// Set the version constraint, for modules from a registry.
+    pub fn set_version(self, v: impl ToString) -> Self {
+        self.0.data.borrow_mut().version = Some(v.to_string());
+        self
+    }
+
+    /// Set an input variable of the module.
+    pub fn set_input(self, name: impl ToString, v: impl Into<AnyField>) -> Self {
+        self.0.data.borrow_mut().inputs.insert(name.to_string(), v.into());
+        self
+    }
+
+    /// Pass a provider configuration to the module (`providers`). `name` is the
+    /// provider name in the module (ex: `aws` or `aws.west`) and `provider_ref` is the
+    /// `provider_ref()` of a provider in this stack.
+    pub fn set_provider(self, name: impl ToString, provider_ref: impl ToString) -> Self {
+        self.0.data.borrow_mut().providers.insert(name.to_string(), provider_ref.to_string());
+        self
+    }
+
+    pub fn depends_on(self, dep: &impl Referable) -> Self {
+        self.0.data.borrow_mut().depends_on.push(dep.extract_ref());
+        self
+    }
+
+    /// Get a reference to the module, for using its outputs.
+    pub fn module_ref(&self) -> ModuleRef {
+        ModuleRef::from_name(self.0.shared.clone(), &self.0.tf_id)
+    }
+}
+
+impl Referable for ModuleCall {
+    fn extract_ref(&self) -> String {
+        format!("module.{}", self.0.tf_id)
+    }
+}
+
+impl Module for ModuleCall_ {
+    fn extract_tf_id(&self) -> String {
+        self.tf_id.clone()
+    }
+
+    fn extract_value(&self) -> Value {
+        serde_json::to_value(&self.data).unwrap()
+    }
+}
+
+/// Create a new module call. Local `source` paths (ex: `./modules/vpc`) are
+/// resolved relative to the directory the stack's config is written to - for
+/// `Stack::run`, the run directory - not to the current directory of the process.
+/// Running the stack fails with `RunError::ModuleSourceMissing` if a local source
+/// doesn't exist there.
+pub struct BuildModule {
+    pub tf_id: String,
+    /// A local path, registry address (ex: `terraform-aws-modules/vpc/aws`), or other
+    /// module source
+    pub source: String,
+}
+
+impl BuildModule {
+    pub fn build(self, stack: &mut Stack) -> ModuleCall {
+        let out = ModuleCall(Rc::new(ModuleCall_ {
+            shared: stack.shared.clone(),
+            tf_id: self.tf_id,
+            data: RefCell::new(ModuleCallData {
+                source: self.source,
+                version: None,
+                depends_on: vec![],
+                providers: Default::default(),
+                inputs: Default::default(),
+            }),
+        }));
+        stack.add_module(out.0.clone());
+        out
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    StackShared,
+    PrimType,
+    prim_ref::PrimExpr,
+    list_ref::ListRef,
+    rec_ref::RecRef,
+    set_ref::SetRef,
+    any_ref::AnyRef,
+    Ref,
+    Referable,
+};
+
+/// A reference to a Terraform module call (`module.<name>`), for using its
+/// outputs. Modules are opaque, so specify the type you expect each output to
+/// have.
+///
+/// Get one from a module call built with `BuildModule`, or with `from_name` for a
+/// module call defined outside the stack (in a `.tf` file in the same directory as
+/// the stack's config).
+pub struct ModuleRef {
+    pub(crate) shared: StackShared,
+    pub(crate) base: String,
+}
+
+impl Ref for ModuleRef {
+    fn new(shared: StackShared, base: String) -> Self {
+        ModuleRef {
+            shared: shared,
+            base: base,
+        }
+    }
+}
+
+impl ModuleRef {
+    /// Create a reference to a module call by its name (the `<name>` in
+    /// `module.<name>`).
+    pub fn from_name(shared: StackShared, name: &str) -> Self {
+        Self::new(shared, format!("module.{}", name))
+    }
+
+    /// Refer to a primitive output.
+    pub fn output<T: PrimType>(&self, name: &str) -> PrimExpr<T> {
+        PrimExpr::new(self.shared.clone(), format!("{}.{}", self.base, name))
+    }
+
+    /// Refer to a list output.
+    pub fn output_list<T: Ref>(&self, name: &str) -> ListRef<T> {
+        ListRef::new(self.shared.clone(), format!("{}.{}", self.base, name))
+    }
+
+    /// Refer to a set output.
+    pub fn output_set<T: Ref>(&self, name: &str) -> SetRef<T> {
+        SetRef::new(self.shared.clone(), format!("{}.{}", self.base, name))
+    }
+
+    /// Refer to a map output.
+    pub fn output_map<T: Ref>(&self, name: &str) -> RecRef<T> {
+        RecRef::new(self.shared.clone(), format!("{}.{}", self.base, name))
+    }
+
+    /// Refer to an output of any other type (ex: an object).
+    pub fn output_any(&self, name: &str) -> AnyRef {
+        AnyRef::new(self.shared.clone(), format!("{}.{}", self.base, name))
+    }
+}
+
+impl Referable for ModuleRef {
+    fn extract_ref(&self) -> String {
+        self.base.clone()
+    }
+}
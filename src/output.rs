@@ -17,7 +17,7 @@ use crate::{
     SerdeSkipDefault,
 };
 
-pub(crate) trait Output {
+pub trait Output {
     fn extract_tf_id(&self) -> String;
     fn extract_value(&self) -> Value;
 }
@@ -68,7 +68,7 @@ impl<T: PrimType + 'static> BuildOutput<T> {
                 value: self.value,
             }),
         });
-        stack.outputs.push(out.clone());
+        stack.add_output(out.clone());
         out
     }
 }
@@ -0,0 +1,44 @@
+//! The types and functions used in most stacks, for `use terrars::prelude::*;`.
+//! Generated provider bindings aren't included - import those from their own
+//! crates or modules - and neither are the `tf_*` function helpers (in
+//! `terrars::helpers`).
+pub use crate::{
+    // Stack
+    BuildStack,
+    Stack,
+    RunOptions,
+    RunResult,
+    RunError,
+    // Variables and outputs
+    BuildVariable,
+    Variable,
+    BuildOutput,
+    // Modules
+    BuildModule,
+    ModuleCall,
+    // Field values
+    PrimField,
+    ListField,
+    SetField,
+    RecField,
+    AnyField,
+    BlockAssignable,
+    DynamicBlock,
+    IgnoreChanges,
+    IgnoreChangesAll,
+    primvec,
+    primmap,
+    // References and expressions
+    PrimExpr,
+    ListRef,
+    SetRef,
+    RecRef,
+    AnyRef,
+    ModuleRef,
+    MapKV,
+    Expr,
+    Ref,
+    Referable,
+    Func,
+    ToFuncArg,
+};
@@ -5,7 +5,10 @@ use std::{
 use serde::{
     Serialize,
 };
-use crate::utils::REPLACE_EXPRS;
+use crate::{
+    utils::REPLACE_EXPRS,
+    escape_template_literal,
+};
 
 pub trait TfPrimitiveType {
     fn extract_variable_type() -> String;
@@ -21,7 +24,7 @@ impl TfPrimitiveType for String {
     }
 
     fn to_expr_raw(&self) -> String {
-        return format!("\"{}\"", self.replace("\\", "\\\\").replace("\"", "\\\""));
+        return format!("\"{}\"", escape_template_literal(self));
     }
 
     fn serialize2<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -33,6 +36,10 @@ impl TfPrimitiveType for String {
                 for (k, v) in vs {
                     out = out.replace(k, v);
                 }
+                if out == "${null}" {
+                    // Only possible from an expression, literals are escaped
+                    return serializer.serialize_none();
+                }
                 out.serialize(serializer)
             } else {
                 self.serialize(serializer)
@@ -63,7 +70,7 @@ impl TfPrimitiveType for bool {
 
 impl TfPrimitiveType for i64 {
     fn extract_variable_type() -> String {
-        "int".into()
+        "number".into()
     }
 
     fn to_expr_raw(&self) -> String {
@@ -79,7 +86,7 @@ impl TfPrimitiveType for i64 {
 
 impl TfPrimitiveType for f64 {
     fn extract_variable_type() -> String {
-        "float".into()
+        "number".into()
     }
 
     fn to_expr_raw(&self) -> String {
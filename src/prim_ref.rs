@@ -38,6 +38,23 @@ impl<T: PrimType> PrimExpr<T> {
     pub fn raw(&self) -> String {
         self.1.clone()
     }
+
+    /// Convert the value to a string (`tostring`).
+    pub fn to_string_expr(&self) -> PrimExpr<String> {
+        PrimExpr(self.0.clone(), format!("tostring({})", self.1), Default::default())
+    }
+
+    /// Convert the value to a number (`tonumber`), ex: a number returned as a string.
+    /// Fails when applying if the value isn't a valid number.
+    pub fn to_number(&self) -> PrimExpr<f64> {
+        PrimExpr(self.0.clone(), format!("tonumber({})", self.1), Default::default())
+    }
+
+    /// Convert the value to a bool (`tobool`). Only `true`, `false`, `"true"`, and
+    /// `"false"` can be converted.
+    pub fn to_bool(&self) -> PrimExpr<bool> {
+        PrimExpr(self.0.clone(), format!("tobool({})", self.1), Default::default())
+    }
 }
 
 manual_expr_impls!(PrimExpr);
@@ -80,3 +97,22 @@ impl<T: PrimType> ToObjMappable for PrimExpr<T> {
         MapRecRef::new(self.0, base, k.1, self.1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BuildStack,
+        PrimExpr,
+    };
+
+    #[test]
+    fn conversions() {
+        let stack = BuildStack {}.build();
+        let n: PrimExpr<f64> = stack.expr("var.n");
+        assert_eq!(n.to_string_expr().raw(), "tostring(var.n)");
+        let s = stack.expr_lit("1\"${x}".to_string());
+        assert_eq!(s.to_number().raw(), "tonumber(\"1\\\"$${x}\")");
+        assert_eq!(s.to_bool().raw(), "tobool(\"1\\\"$${x}\")");
+        assert_eq!(n.to_string_expr().to_number().raw(), "tonumber(tostring(var.n))");
+    }
+}
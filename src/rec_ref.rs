@@ -9,6 +9,7 @@ use crate::{
     },
     MapKV,
     Ref,
+    CollectionRef,
 };
 
 pub trait ToObjMappable {
@@ -39,6 +40,14 @@ impl<T: Ref> Ref for RecRef<T> {
     }
 }
 
+impl<T: Ref> CollectionRef for RecRef<T> {
+    type Element = T;
+
+    fn collection_base(&self) -> (StackShared, String) {
+        (self.shared.clone(), self.base.clone())
+    }
+}
+
 impl<T: Ref> RecRef<T> {
     pub fn get(&self, key: impl ToString) -> T {
         T::new(self.shared.clone(), format!("{}[\"{}\"]", &self.base, key.to_string()))
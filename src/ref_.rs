@@ -3,3 +3,12 @@ use crate::StackShared;
 pub trait Ref {
     fn new(shared: StackShared, base: String) -> Self;
 }
+
+/// Implemented by references to collections (lists, sets, records) that can be
+/// iterated in a `for_each`.
+pub trait CollectionRef {
+    type Element: Ref;
+
+    /// Returns the stack and the raw expression of the collection.
+    fn collection_base(&self) -> (StackShared, String);
+}
@@ -1,7 +1,10 @@
 use std::marker::PhantomData;
 use crate::{
     StackShared,
-    ref_::Ref,
+    ref_::{
+        Ref,
+        CollectionRef,
+    },
     list_ref::ToListMappable,
     MapKV,
     ListRef,
@@ -24,6 +27,14 @@ impl<T: Ref> Ref for SetRef<T> {
     }
 }
 
+impl<T: Ref> CollectionRef for SetRef<T> {
+    type Element = T;
+
+    fn collection_base(&self) -> (StackShared, String) {
+        (self.shared.clone(), self.base.clone())
+    }
+}
+
 impl<T: Ref> SetRef<T> {
     pub fn map<O: ToListMappable>(&self, inner: impl FnOnce(MapKV<T>) -> O) -> O::O {
         let out = inner(MapKV::new(self.shared.clone()));
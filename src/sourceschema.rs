@@ -0,0 +1,184 @@
+//! Types for the provider schemas output by `terraform providers schema -json`,
+//! used to generate bindings. These can also be used to inspect schemas in other
+//! tools.
+use std::collections::BTreeMap;
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SchemaError {
+    #[error("Unsupported attribute behavior (required: {0}, optional: {1}, computed: {2})")]
+    UnsupportedBehavior(bool, bool, bool),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProviderSchemas {
+    #[serde(default)]
+    pub provider_schemas: BTreeMap<String, ProviderSchema>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProviderSchema {
+    pub provider: Provider,
+    #[serde(default)]
+    pub data_source_schemas: BTreeMap<String, SchemaItem>,
+    #[serde(default)]
+    pub resource_schemas: BTreeMap<String, SchemaItem>,
+    #[serde(default)]
+    pub ephemeral_resource_schemas: BTreeMap<String, SchemaItem>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Provider {
+    pub block: Block,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SchemaItem {
+    pub block: Block,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Block {
+    #[serde(default)]
+    pub attributes: BTreeMap<String, Value>,
+    #[serde(default)]
+    pub block_types: BTreeMap<String, NestedBlock>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ScalarTypeKey {
+    Number,
+    Integer,
+    String,
+    Bool,
+    Dynamic,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum AggCollTypeKey {
+    Set,
+    List,
+    #[serde(rename = "map")]
+    Rec,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AggCollType(pub AggCollTypeKey, pub ValueSchema);
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum AggObjTypeKey {
+    Object,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AggObjType(pub AggObjTypeKey, pub BTreeMap<String, ValueSchema>);
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ValueSchema {
+    Simple(ScalarTypeKey),
+    AggColl(Box<AggCollType>),
+    AggObj(Box<AggObjType>),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum DescriptionKind {
+    Plain,
+    Markdown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueBehaviorHelper {
+    UserRequired,
+    UserOptional,
+    Computed,
+    UserOptionalComputed,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Value {
+    pub r#type: Option<ValueSchema>,
+    // this is a terraform schema v1 only variation, mutually exclusive with type
+    pub nested_type: Option<ValueSchemaNested>,
+    pub description: Option<String>,
+    pub description_kind: Option<DescriptionKind>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default)]
+    pub computed: bool,
+    #[serde(default)]
+    pub sensitive: bool,
+    // terraform 1.11+, not stored in state
+    #[serde(default)]
+    pub write_only: bool,
+}
+
+impl Value {
+    pub fn behavior(&self) -> Result<ValueBehaviorHelper, SchemaError> {
+        match (self.required, self.optional, self.computed) {
+            (true, false, false) => Ok(ValueBehaviorHelper::UserRequired),
+            (false, true, false) => Ok(ValueBehaviorHelper::UserOptional),
+            (false, false, true) => Ok(ValueBehaviorHelper::Computed),
+            (false, true, true) => Ok(ValueBehaviorHelper::UserOptionalComputed),
+            _ => Err(SchemaError::UnsupportedBehavior(self.required, self.optional, self.computed)),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ValueSchemaNested {
+    pub nesting_mode: NestingMode,
+    pub attributes: BTreeMap<String, Value>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct NestedBlock {
+    pub block: Block,
+    pub nesting_mode: NestingMode,
+    pub min_items: Option<u64>,
+    pub max_items: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum NestingMode {
+    List,
+    Set,
+    Single,
+    // blocks only, like single but always present
+    Group,
+    Map,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        SchemaError,
+        Value,
+        ValueBehaviorHelper,
+    };
+
+    #[test]
+    fn behavior() {
+        let value = |v: serde_json::Value| -> Value {
+            serde_json::from_value(v).unwrap()
+        };
+        assert_eq!(
+            value(serde_json::json!({ "type": "string", "optional": true, "computed": true })).behavior().unwrap(),
+            ValueBehaviorHelper::UserOptionalComputed
+        );
+        assert!(
+            matches!(
+                value(serde_json::json!({ "type": "string", "required": true, "computed": true })).behavior(),
+                Err(SchemaError::UnsupportedBehavior(true, false, true))
+            )
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Types for reading Terraform state files (`.tfstate`, format version 4) without
+//! running Terraform.
+use std::collections::BTreeMap;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TerraformState {
+    /// The state format version, 4 for all current versions of Terraform
+    pub version: u64,
+    pub terraform_version: String,
+    /// Incremented every time the state is written
+    pub serial: u64,
+    /// Unique id of the state, assigned when it's first created
+    pub lineage: String,
+    #[serde(default)]
+    pub outputs: BTreeMap<String, StateOutput>,
+    #[serde(default)]
+    pub resources: Vec<StateResource>,
+}
+
+impl TerraformState {
+    /// Find a resource by its address (ex: `aws_instance.web`,
+    /// `data.aws_ami.ubuntu`, `module.net.aws_vpc.main`).
+    pub fn resource(&self, address: &str) -> Option<&StateResource> {
+        self.resources.iter().find(|r| r.address() == address)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StateOutput {
+    pub value: Value,
+    /// The Terraform type, in the provider schema json format (ex: `"string"`, `["list",
+    /// "string"]`)
+    #[serde(rename = "type")]
+    pub type_: Value,
+    #[serde(default)]
+    pub sensitive: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceMode {
+    Managed,
+    Data,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StateResource {
+    /// The module the resource is in (ex: `module.net`), missing for the root module
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    pub mode: ResourceMode,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub name: String,
+    /// The provider configuration address (ex:
+    /// `provider["registry.terraform.io/hashicorp/aws"].west`)
+    pub provider: String,
+    /// One instance normally, or one per `count` index or `for_each` key
+    pub instances: Vec<StateInstance>,
+}
+
+impl StateResource {
+    /// The resource's address, as used in expressions and commands (ex:
+    /// `aws_instance.web`).
+    pub fn address(&self) -> String {
+        let mut out = String::new();
+        if let Some(module) = &self.module {
+            out.push_str(module);
+            out.push('.');
+        }
+        if self.mode == ResourceMode::Data {
+            out.push_str("data.");
+        }
+        out.push_str(&self.type_);
+        out.push('.');
+        out.push_str(&self.name);
+        out
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StateInstance {
+    /// The `count` index (a number) or `for_each` key (a string), missing for single
+    /// instances
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_key: Option<Value>,
+    pub schema_version: u64,
+    /// The attribute values as json, in the structure of the provider's schema
+    pub attributes: Value,
+    #[serde(default)]
+    pub sensitive_attributes: Vec<Value>,
+    /// Addresses of resources this instance depended on when it was last applied
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Set if the instance is tainted (`tainted`) and will be replaced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+/// Parse a Terraform state file (ex: the contents of `state.tfstate` in a stack's
+/// run directory). Only state format version 4 is supported.
+pub fn parse_state(bytes: &[u8]) -> Result<TerraformState, serde_json::Error> {
+    let out: TerraformState = serde_json::from_slice(bytes)?;
+    if out.version != 4 {
+        return Err(
+            <serde_json::Error as serde::de::Error>::custom(
+                format!("Unsupported state format version {}, only version 4 is supported", out.version),
+            ),
+        );
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::{
+        parse_state,
+        ResourceMode,
+    };
+
+    #[test]
+    fn parse_v4() {
+        let state = parse_state(include_bytes!("../tests/fixtures/state.tfstate")).unwrap();
+        assert_eq!(state.serial, 7);
+        assert_eq!(state.outputs["ip"].value, json!("10.0.0.5"));
+        assert!(!state.outputs["ip"].sensitive);
+        assert!(state.outputs["password"].sensitive);
+        assert_eq!(
+            state.resources.iter().map(|r| r.address()).collect::<Vec<_>>(),
+            vec!["data.aws_ami.ubuntu", "aws_instance.web", "module.net.aws_vpc.main"]
+        );
+        let ami = state.resource("data.aws_ami.ubuntu").unwrap();
+        assert_eq!(ami.mode, ResourceMode::Data);
+        assert_eq!(ami.instances[0].index_key, None);
+        let web = state.resource("aws_instance.web").unwrap();
+        assert_eq!(web.instances[1].index_key, Some(json!(1)));
+        assert_eq!(web.instances[1].attributes["private_ip"], json!("10.0.0.6"));
+        assert_eq!(web.instances[1].status.as_deref(), Some("tainted"));
+        assert_eq!(web.instances[0].dependencies, vec!["data.aws_ami.ubuntu"]);
+        let vpc = state.resource("module.net.aws_vpc.main").unwrap();
+        assert_eq!(vpc.module.as_deref(), Some("module.net"));
+        assert_eq!(vpc.instances[0].index_key, Some(json!("primary")));
+        assert!(state.resource("aws_vpc.main").is_none());
+    }
+
+    #[test]
+    fn reject_invalid() {
+        let v3 =
+            serde_json::to_vec(
+                &json!({
+                    "version": 3,
+                    "terraform_version": "0.11.14",
+                    "serial": 1,
+                    "lineage": "x",
+                    "modules": []
+                }),
+            ).unwrap();
+        assert!(parse_state(&v3).is_err());
+        assert!(parse_state(b"{\"version\": 4").is_err());
+        assert!(parse_state(b"{\"version\": 4}").is_err());
+    }
+}
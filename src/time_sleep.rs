@@ -0,0 +1,179 @@
+use std::{
+    cell::{
+        RefCell,
+    },
+    rc::Rc,
+};
+use serde::{
+    Serialize,
+};
+use serde_json::{
+    Value,
+};
+use crate::{
+    PrimExpr,
+    PrimField,
+    RecField,
+    RecRef,
+    Ref,
+    Referable,
+    Resource,
+    Resource_,
+    Stack,
+    StackShared,
+};
+
+#[derive(Serialize)]
+struct TimeSleepData {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    create_duration: Option<PrimField<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    destroy_duration: Option<PrimField<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    triggers: Option<RecField<PrimField<String>>>,
+}
+
+struct TimeSleep_ {
+    shared: StackShared,
+    tf_id: String,
+    data: RefCell<TimeSleepData>,
+}
+
+/// A `time_sleep` resource from the `hashicorp/time` provider, which waits before
+/// finishing creation or destruction. Make other resources depend on it to delay
+/// them, for instance while permissions propagate. Terraform installs the `time`
+/// provider automatically.
+#[derive(Clone)]
+pub struct TimeSleep(Rc<TimeSleep_>);
+
+impl TimeSleep {
+    fn shared(&self) -> &StackShared {
+        &self.0.shared
+    }
+
+    pub fn depends_on(self, dep: &impl Referable) -> Self {
+        self.0.data.borrow_mut().depends_on.push(dep.extract_ref());
+        self
+    }
+
+    /// Set how long to wait when creating, like `30s` or `5m`.
+    pub fn set_create_duration(self, v: impl Into<PrimField<String>>) -> Self {
+        self.0.data.borrow_mut().create_duration = Some(v.into());
+        self
+    }
+
+    /// Set how long to wait when destroying, like `30s` or `5m`.
+    pub fn set_destroy_duration(self, v: impl Into<PrimField<String>>) -> Self {
+        self.0.data.borrow_mut().destroy_duration = Some(v.into());
+        self
+    }
+
+    /// Set values that recreate the resource (and wait again) when they change.
+    pub fn set_triggers(self, v: impl Into<RecField<PrimField<String>>>) -> Self {
+        self.0.data.borrow_mut().triggers = Some(v.into());
+        self
+    }
+
+    /// Get a reference to the creation timestamp (RFC 3339), available after waiting.
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.id", self.extract_ref()))
+    }
+
+    /// Get a reference to the triggers, available after waiting. Use these instead of
+    /// the original values to make sure the dependent resources wait.
+    pub fn triggers(&self) -> RecRef<PrimExpr<String>> {
+        RecRef::new(self.shared().clone(), format!("{}.triggers", self.extract_ref()))
+    }
+}
+
+impl Referable for TimeSleep {
+    fn extract_ref(&self) -> String {
+        format!("{}.{}", self.0.extract_resource_type(), self.0.extract_tf_id())
+    }
+}
+
+impl Resource for TimeSleep { }
+
+impl Resource_ for TimeSleep_ {
+    fn extract_resource_type(&self) -> String {
+        "time_sleep".into()
+    }
+
+    fn extract_tf_id(&self) -> String {
+        self.tf_id.clone()
+    }
+
+    fn extract_value(&self) -> Value {
+        serde_json::to_value(&self.data).unwrap()
+    }
+}
+
+/// Create a new `time_sleep` resource.
+pub struct BuildTimeSleep {
+    pub tf_id: String,
+}
+
+impl BuildTimeSleep {
+    pub fn build(self, stack: &mut Stack) -> TimeSleep {
+        let out = TimeSleep(Rc::new(TimeSleep_ {
+            shared: stack.shared.clone(),
+            tf_id: self.tf_id,
+            data: RefCell::new(TimeSleepData {
+                depends_on: vec![],
+                create_duration: None,
+                destroy_duration: None,
+                triggers: None,
+            }),
+        }));
+        stack.add_resource(out.0.clone());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        path::Path,
+    };
+    use serde_json::{
+        json,
+        Value,
+    };
+    use crate::{
+        BuildStack,
+        Referable,
+    };
+    use super::BuildTimeSleep;
+
+    #[test]
+    fn serialize() {
+        let mut stack = BuildStack {}.build();
+        let role_arn = stack.expr::<String>("aws_iam_role.r.arn");
+        let sleep =
+            BuildTimeSleep { tf_id: "wait".into() }
+                .build(&mut stack)
+                .set_create_duration("30s")
+                .set_destroy_duration("5m")
+                .set_triggers(HashMap::from([("role".to_string(), role_arn.into())]));
+        let after = BuildTimeSleep { tf_id: "after".into() }.build(&mut stack).depends_on(&sleep);
+        assert_eq!(sleep.extract_ref(), "time_sleep.wait");
+        assert_eq!(sleep.triggers().get("role").raw(), "time_sleep.wait.triggers[\"role\"]");
+        assert_eq!(after.id().raw(), "time_sleep.after.id");
+        let out: Value = serde_json::from_slice(&stack.serialize(Path::new("state.tfstate")).unwrap()).unwrap();
+        assert_eq!(out["resource"]["time_sleep"], json!({
+            "wait": {
+                "create_duration": "30s",
+                "destroy_duration": "5m",
+                "triggers": {
+                    "role": "${aws_iam_role.r.arn}"
+                }
+            },
+            "after": {
+                "depends_on": ["time_sleep.wait"]
+            }
+        }));
+    }
+}
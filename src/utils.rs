@@ -5,5 +5,25 @@ use std::{
 };
 
 thread_local!{
-    pub(crate) static REPLACE_EXPRS: RefCell<Option<Vec<(String, String)>>> = RefCell::new(None);
+    pub(crate) static REPLACE_EXPRS: RefCell<Option<Vec<(String, String)>>> = const { RefCell::new(None) };
+}
+
+/// Sets `REPLACE_EXPRS` while values are converted for serialization, and clears it
+/// when dropped so an early return doesn't leave it set for the next serialization
+/// on the thread.
+pub(crate) struct ReplaceExprsGuard;
+
+impl ReplaceExprsGuard {
+    pub(crate) fn new(replace_exprs: Vec<(String, String)>) -> Self {
+        REPLACE_EXPRS.with(move |f| {
+            *f.borrow_mut() = Some(replace_exprs);
+        });
+        ReplaceExprsGuard
+    }
+}
+
+impl Drop for ReplaceExprsGuard {
+    fn drop(&mut self) {
+        REPLACE_EXPRS.with(|f| *f.borrow_mut() = None);
+    }
 }
@@ -25,7 +25,7 @@ use crate::{
     Stack,
 };
 
-pub(crate) trait VariableTrait {
+pub trait VariableTrait {
     fn extract_tf_id(&self) -> String;
     fn extract_value(&self) -> Value;
 }
@@ -38,6 +38,8 @@ struct VariableImplData {
     pub nullable: PrimField<bool>,
     #[serde(skip_serializing_if = "SerdeSkipDefault::is_default")]
     pub sensitive: PrimField<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
 }
 
 struct Variable_<T: PrimType> {
@@ -70,6 +72,14 @@ impl<T: PrimType> Variable<T> {
         self.0.data.borrow_mut().sensitive = v.into();
         self
     }
+
+    /// Set the value used when the variable isn't provided. Terraform uses the
+    /// default verbatim without interpolation, so it can't be an expression
+    /// (`${...}` in strings is kept as-is).
+    pub fn set_default(self, v: impl Into<T>) -> Self {
+        self.0.data.borrow_mut().default = Some(serde_json::to_value(v.into()).unwrap());
+        self
+    }
 }
 
 impl<T: PrimType> Expr<T> for Variable<T> {
@@ -104,10 +114,69 @@ impl BuildVariable {
                 r#type: T::extract_variable_type(),
                 nullable: false.into(),
                 sensitive: false.into(),
+                default: None,
             }),
             _p: Default::default(),
         }));
-        stack.variables.push(out.0.clone());
+        stack.add_variable(out.0.clone());
         out
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use serde_json::{
+        json,
+        Value,
+    };
+    use crate::{
+        BuildStack,
+        BuildVariable,
+        Stack,
+    };
+
+    fn variables(stack: &Stack) -> Value {
+        let out: Value = serde_json::from_slice(&stack.serialize(Path::new("state.tfstate")).unwrap()).unwrap();
+        return out["variable"].clone();
+    }
+
+    #[test]
+    fn default_literal() {
+        let stack = &mut BuildStack {}.build();
+        BuildVariable { tf_id: "s".into() }.build::<String>(stack).set_default("${not.an.expr}");
+        BuildVariable { tf_id: "n".into() }.build::<i64>(stack).set_default(3);
+        BuildVariable { tf_id: "b".into() }.build::<bool>(stack).set_default(true);
+        assert_eq!(variables(stack), json!({
+            "s": {
+                "type": "string",
+                "nullable": false,
+                "default": "${not.an.expr}"
+            },
+            "n": {
+                "type": "number",
+                "nullable": false,
+                "default": 3
+            },
+            "b": {
+                "type": "bool",
+                "nullable": false,
+                "default": true
+            }
+        }));
+    }
+
+    #[test]
+    fn type_from_rust_type() {
+        let stack = &mut BuildStack {}.build();
+        BuildVariable { tf_id: "s".into() }.build::<String>(stack);
+        BuildVariable { tf_id: "b".into() }.build::<bool>(stack);
+        BuildVariable { tf_id: "i".into() }.build::<i64>(stack);
+        BuildVariable { tf_id: "f".into() }.build::<f64>(stack);
+        let out = variables(stack);
+        assert_eq!(out["s"]["type"], "string");
+        assert_eq!(out["b"]["type"], "bool");
+        assert_eq!(out["i"]["type"], "number");
+        assert_eq!(out["f"]["type"], "number");
+    }
+}
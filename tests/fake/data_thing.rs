@@ -0,0 +1,248 @@
+use serde::Serialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+use terrars::*;
+use super::provider::ProviderFake;
+
+#[derive(Serialize)]
+struct DataThingData {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+    #[serde(skip_serializing_if = "SerdeSkipDefault::is_default")]
+    provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    for_each: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<PrimField<i64>>,
+    id: PrimField<String>,
+    #[serde(flatten)]
+    __terrars_raw: std::collections::BTreeMap<String, AnyField>,
+}
+
+struct DataThing_ {
+    shared: StackShared,
+    tf_id: String,
+    data: RefCell<DataThingData>,
+}
+
+#[derive(Clone)]
+pub struct DataThing(Rc<DataThing_>);
+
+impl DataThing {
+    fn shared(&self) -> &StackShared {
+        &self.0.shared
+    }
+
+    fn attr_ref(&self, attr: &str) -> String {
+        format!("{}.{}", self.extract_ref(), attr)
+    }
+
+    pub fn depends_on(self, dep: &impl Referable) -> Self {
+        self.0.data.borrow_mut().depends_on.push(dep.extract_ref());
+        self
+    }
+
+    pub fn set_provider(&self, provider: &ProviderFake) -> &Self {
+        self.0.data.borrow_mut().provider = Some(provider.provider_ref());
+        self
+    }
+
+    #[doc =
+        "Read `v` instances of the datasource (`count`). Use `index` to refer to an instance. Can't be combined with `for_each` (ex: creating the datasource with `map`)."]
+    pub fn set_count(self, v: impl Into<PrimField<i64>>) -> Self {
+        self.0.data.borrow_mut().count = Some(v.into());
+        self
+    }
+
+    #[doc =
+        "Only read the datasource if `cond` is true (sets `count` to 1 or 0). Use `enabled_ref` to refer to the datasource. Can't be combined with `for_each` (ex: creating the datasource with `map`)."]
+    pub fn set_enabled_if(self, cond: &impl Expr<bool>) -> Self {
+        let count = self.shared().add_sentinel(&format!("{} ? 1 : 0", cond.expr_raw().1));
+        self.0.data.borrow_mut().count = Some(PrimField::Sentinel(count));
+        self
+    }
+
+    #[doc =
+        "Refer to the datasource when it was made conditional with `set_enabled_if`. Attributes are `null` when the datasource is disabled. Values nested in blocks aren't guarded, so only refer to them when the condition is true."]
+    pub fn enabled_ref(&self) -> DataThingRef {
+        DataThingRef {
+            shared: self.shared().clone(),
+            base: self.extract_ref(),
+            guarded: true,
+        }
+    }
+
+    #[doc = "Refer to one instance of a datasource read with `set_count`."]
+    pub fn index(&self, i: usize) -> DataThingRef {
+        DataThingRef::new(self.shared().clone(), format!("{}[{}]", self.extract_ref(), i))
+    }
+
+    #[doc =
+        "Refer to one instance of a datasource read with `for_each` (ex: with `map`) by its key. Use `extract_ref` on the result to get the address for `RunOptions::targets` or `RunOptions::replace`."]
+    pub fn instance(&self, key: &str) -> DataThingRef {
+        DataThingRef::new(
+            self.shared().clone(),
+            format!("{}[{}]", self.extract_ref(), key.to_string().to_expr_raw()),
+        )
+    }
+
+    #[doc =
+        "Set attributes and blocks from a value that serializes to a json object in Terraform's json syntax (ex: the datasource body from an existing `.tf.json` file). This bypasses type checking and takes precedence over values set with the typed setters. Strings are escaped like other literal strings, but may contain expressions converted to strings (ex: with `to_string`). Panics if the value doesn't serialize to an object."]
+    pub fn set_from(self, v: impl Serialize) -> Self {
+        match serde_json::to_value(v).unwrap() {
+            serde_json::Value::Object(o) => self
+                .0
+                .data
+                .borrow_mut()
+                .__terrars_raw
+                .extend(o.into_iter().map(|(k, v)| (k, AnyField::Literal(v)))),
+            _ => panic!("set_from value must serialize to a json object"),
+        }
+        self
+    }
+
+    #[doc = "Get a reference to the value of field `id` after provisioning.\n"]
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("id"))
+    }
+
+    #[doc = "Get a reference to the value of field `out` after provisioning.\n"]
+    pub fn out(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("out"))
+    }
+}
+
+impl Referable for DataThing {
+    fn extract_ref(&self) -> String {
+        format!("data.{}.{}", self.0.extract_datasource_type(), self.0.extract_tf_id())
+    }
+}
+
+impl Datasource for DataThing { }
+
+impl std::fmt::Debug for DataThing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(stringify!(DataThing)).field("type", &"fake_thing").field("tf_id", &self.0.tf_id).finish()
+    }
+}
+
+impl ToListMappable for DataThing {
+    type O = ListRef<DataThingRef>;
+
+    fn do_map(self, base: String) -> Self::O {
+        self.0.data.borrow_mut().for_each = Some(format!("${{{}}}", base));
+        ListRef::new(self.0.shared.clone(), self.extract_ref())
+    }
+}
+
+impl Datasource_ for DataThing_ {
+    fn extract_datasource_type(&self) -> String {
+        "fake_thing".into()
+    }
+
+    fn extract_tf_id(&self) -> String {
+        self.tf_id.clone()
+    }
+
+    fn extract_value(&self) -> serde_json::Value {
+        serde_json::to_value(&self.data).unwrap()
+    }
+}
+
+pub struct BuildDataThing {
+    pub tf_id: String,
+    pub id: PrimField<String>,
+}
+
+impl BuildDataThing {
+    fn create(self, stack: &Stack) -> DataThing {
+        DataThing(Rc::new(DataThing_ {
+            shared: stack.shared.clone(),
+            tf_id: self.tf_id,
+            data: RefCell::new(DataThingData {
+                depends_on: core::default::Default::default(),
+                provider: None,
+                for_each: None,
+                count: None,
+                id: self.id,
+                __terrars_raw: Default::default(),
+            }),
+        }))
+    }
+
+    pub fn build(self, stack: &mut Stack) -> DataThing {
+        let out = self.create(stack);
+        stack.add_datasource(out.0.clone());
+        out
+    }
+
+    #[doc =
+        "Like `build` but fails instead of adding the datasource if `tf_id` isn't a valid Terraform identifier or is already used by another datasource of the same type."]
+    pub fn try_build(self, stack: &mut Stack) -> Result<DataThing, StackError> {
+        let out = self.create(stack);
+        stack.try_add_datasource(out.0.clone())?;
+        Ok(out)
+    }
+}
+
+pub struct DataThingRef {
+    shared: StackShared,
+    base: String,
+    guarded: bool,
+}
+
+impl Ref for DataThingRef {
+    fn new(shared: StackShared, base: String) -> Self {
+        Self {
+            shared: shared,
+            base: base,
+            guarded: false,
+        }
+    }
+}
+
+impl DataThingRef {
+    #[doc =
+        "Create a reference to an existing datasource by its address (ex: `data.type.name`), for instance one defined outside this stack."]
+    pub fn from_address(shared: StackShared, address: &str) -> Self {
+        Self::new(shared, address.to_string())
+    }
+
+    #[doc = "Refer to one instance of a datasource read with `count`."]
+    pub fn index(&self, i: usize) -> Self {
+        Self::new(self.shared.clone(), format!("{}[{}]", self.base, i))
+    }
+
+    #[doc = "Refer to one instance of a datasource read with `for_each` by its key."]
+    pub fn instance(&self, key: &str) -> Self {
+        Self::new(self.shared.clone(), format!("{}[{}]", self.base, key.to_string().to_expr_raw()))
+    }
+
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    fn attr_ref(&self, attr: &str) -> String {
+        if self.guarded {
+            format!("one({}[*].{})", self.base, attr)
+        } else {
+            format!("{}.{}", self.base, attr)
+        }
+    }
+
+    #[doc = "Get a reference to the value of field `id` after provisioning.\n"]
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("id"))
+    }
+
+    #[doc = "Get a reference to the value of field `out` after provisioning.\n"]
+    pub fn out(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("out"))
+    }
+}
+
+impl Referable for DataThingRef {
+    fn extract_ref(&self) -> String {
+        self.base.clone()
+    }
+}
@@ -0,0 +1,1043 @@
+use serde::Serialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+use terrars::*;
+use super::provider::ProviderFake;
+
+#[derive(Serialize)]
+struct DeepData {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<String>,
+    #[serde(skip_serializing_if = "SerdeSkipDefault::is_default")]
+    lifecycle: ResourceLifecycle,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    for_each: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<PrimField<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cblock: Option<Vec<DeepCblockEl>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sblock: Option<Vec<DeepSblockEl>>,
+    #[serde(skip_serializing_if = "DeepDynamic::is_empty")]
+    dynamic: DeepDynamic,
+    #[serde(flatten)]
+    __terrars_raw: std::collections::BTreeMap<String, AnyField>,
+}
+
+struct Deep_ {
+    shared: StackShared,
+    tf_id: String,
+    data: RefCell<DeepData>,
+}
+
+#[derive(Clone)]
+pub struct Deep(Rc<Deep_>);
+
+impl Deep {
+    fn shared(&self) -> &StackShared {
+        &self.0.shared
+    }
+
+    fn attr_ref(&self, attr: &str) -> String {
+        format!("{}.{}", self.extract_ref(), attr)
+    }
+
+    pub fn depends_on(self, dep: &impl Referable) -> Self {
+        self.0.data.borrow_mut().depends_on.push(dep.extract_ref());
+        self
+    }
+
+    pub fn set_provider(self, provider: &ProviderFake) -> Self {
+        self.0.data.borrow_mut().provider = Some(provider.provider_ref());
+        self
+    }
+
+    #[doc =
+        "Create `v` instances of the resource (`count`). Use `index` to refer to an instance. Can't be combined with `for_each` (ex: creating the resource with `map`)."]
+    pub fn set_count(self, v: impl Into<PrimField<i64>>) -> Self {
+        self.0.data.borrow_mut().count = Some(v.into());
+        self
+    }
+
+    #[doc =
+        "Only create the resource if `cond` is true (sets `count` to 1 or 0). Use `enabled_ref` to refer to the resource. Can't be combined with `for_each` (ex: creating the resource with `map`)."]
+    pub fn set_enabled_if(self, cond: &impl Expr<bool>) -> Self {
+        let count = self.shared().add_sentinel(&format!("{} ? 1 : 0", cond.expr_raw().1));
+        self.0.data.borrow_mut().count = Some(PrimField::Sentinel(count));
+        self
+    }
+
+    #[doc =
+        "Refer to the resource when it was made conditional with `set_enabled_if`. Attributes are `null` when the resource is disabled. Values nested in blocks aren't guarded, so only refer to them when the condition is true."]
+    pub fn enabled_ref(&self) -> DeepRef {
+        DeepRef {
+            shared: self.shared().clone(),
+            base: self.extract_ref(),
+            guarded: true,
+        }
+    }
+
+    #[doc = "Refer to one instance of a resource created with `set_count`."]
+    pub fn index(&self, i: usize) -> DeepRef {
+        DeepRef::new(self.shared().clone(), format!("{}[{}]", self.extract_ref(), i))
+    }
+
+    #[doc =
+        "Refer to one instance of a resource created with `for_each` (ex: with `map`) by its key. Use `extract_ref` on the result to get the address for `RunOptions::targets` or `RunOptions::replace`."]
+    pub fn instance(&self, key: &str) -> DeepRef {
+        DeepRef::new(self.shared().clone(), format!("{}[{}]", self.extract_ref(), key.to_string().to_expr_raw()))
+    }
+
+    pub fn set_create_before_destroy(self, v: bool) -> Self {
+        self.0.data.borrow_mut().lifecycle.create_before_destroy = v;
+        self
+    }
+
+    pub fn set_prevent_destroy(self, v: bool) -> Self {
+        self.0.data.borrow_mut().lifecycle.prevent_destroy = v;
+        self
+    }
+
+    pub fn ignore_changes_to_all(self) -> Self {
+        self.0.data.borrow_mut().lifecycle.ignore_changes = Some(IgnoreChanges::All(IgnoreChangesAll::All));
+        self
+    }
+
+    pub fn ignore_changes_to_attr(self, attr: impl ToString) -> Self {
+        {
+            let mut d = self.0.data.borrow_mut();
+            if match &mut d.lifecycle.ignore_changes {
+                Some(i) => match i {
+                    IgnoreChanges::All(_) => {
+                        true
+                    },
+                    IgnoreChanges::Refs(r) => {
+                        r.push(attr.to_string());
+                        false
+                    },
+                },
+                None => true,
+            } {
+                d.lifecycle.ignore_changes = Some(IgnoreChanges::Refs(vec![attr.to_string()]));
+            }
+        }
+        self
+    }
+
+    pub fn replace_triggered_by_resource(self, r: &impl Resource) -> Self {
+        self.0.data.borrow_mut().lifecycle.replace_triggered_by.push(r.extract_ref());
+        self
+    }
+
+    pub fn replace_triggered_by_attr(self, attr: impl ToString) -> Self {
+        self.0.data.borrow_mut().lifecycle.replace_triggered_by.push(attr.to_string());
+        self
+    }
+
+    #[doc = "Remove all attributes set with `ignore_changes_to_attr` or `ignore_changes_to_all`."]
+    pub fn clear_ignore_changes(self) -> Self {
+        self.0.data.borrow_mut().lifecycle.ignore_changes = None;
+        self
+    }
+
+    #[doc = "Remove all references added with `replace_triggered_by_resource` or `replace_triggered_by_attr`."]
+    pub fn clear_replace_triggered_by(self) -> Self {
+        self.0.data.borrow_mut().lifecycle.replace_triggered_by.clear();
+        self
+    }
+
+    #[doc = "Reset all `lifecycle` settings to their defaults."]
+    pub fn clear_lifecycle(self) -> Self {
+        self.0.data.borrow_mut().lifecycle = Default::default();
+        self
+    }
+
+    #[doc =
+        "Merge a value that serializes to a json object into the `lifecycle` block (ex: arguments added in newer Terraform versions). This bypasses the typed lifecycle setters and takes precedence over them. Panics if the value doesn't serialize to an object."]
+    pub fn set_lifecycle_raw(self, v: impl Serialize) -> Self {
+        match serde_json::to_value(v).unwrap() {
+            serde_json::Value::Object(o) => self.0.data.borrow_mut().lifecycle.raw.extend(o),
+            _ => panic!("set_lifecycle_raw value must serialize to a json object"),
+        }
+        self
+    }
+
+    #[doc =
+        "Set attributes and blocks from a value that serializes to a json object in Terraform's json syntax (ex: the resource body from an existing `.tf.json` file). This bypasses type checking and takes precedence over values set with the typed setters. Strings are escaped like other literal strings, but may contain expressions converted to strings (ex: with `to_string`). Panics if the value doesn't serialize to an object."]
+    pub fn set_from(self, v: impl Serialize) -> Self {
+        match serde_json::to_value(v).unwrap() {
+            serde_json::Value::Object(o) => self
+                .0
+                .data
+                .borrow_mut()
+                .__terrars_raw
+                .extend(o.into_iter().map(|(k, v)| (k, AnyField::Literal(v)))),
+            _ => panic!("set_from value must serialize to a json object"),
+        }
+        self
+    }
+
+    #[doc =
+        "Set a single attribute or block in Terraform's json syntax, for last-minute overrides (ex: with a `serde_json::Value` or an expression). Like `set_from`, this bypasses type checking and takes precedence over values set with the typed setters."]
+    pub fn set_data_raw(&self, k: impl ToString, v: impl Into<AnyField>) {
+        self.0.data.borrow_mut().__terrars_raw.insert(k.to_string(), v.into());
+    }
+
+    #[doc =
+        "Get the current value of an attribute or block as it will be serialized, or `None` if it isn't set. Expressions appear as `_TERRARS_SENTINEL*` placeholders."]
+    pub fn get_data_raw(&self, k: &str) -> Option<serde_json::Value> {
+        match serde_json::to_value(&self.0.data).unwrap() {
+            serde_json::Value::Object(mut o) => o.remove(k),
+            _ => None,
+        }
+    }
+
+    #[doc = "Set the field `cblock`.\n"]
+    pub fn set_cblock(self, v: impl Into<BlockAssignable<DeepCblockEl>>) -> Self {
+        match v.into() {
+            BlockAssignable::Literal(v) => {
+                self.0.data.borrow_mut().cblock = Some(v);
+            },
+            BlockAssignable::Dynamic(d) => {
+                self.0.data.borrow_mut().dynamic.cblock = Some(d);
+            },
+        }
+        self
+    }
+
+    #[doc = "Set the field `sblock`.\n"]
+    pub fn set_sblock(self, v: impl Into<BlockAssignable<DeepSblockEl>>) -> Self {
+        match v.into() {
+            BlockAssignable::Literal(v) => {
+                self.0.data.borrow_mut().sblock = Some(v);
+            },
+            BlockAssignable::Dynamic(d) => {
+                self.0.data.borrow_mut().dynamic.sblock = Some(d);
+            },
+        }
+        self
+    }
+
+    #[doc = "Get a reference to the value of field `deep` after provisioning.\n"]
+    pub fn deep(&self) -> ListRef<DeepDeepElRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("deep"))
+    }
+
+    #[doc = "Get a reference to the value of field `id` after provisioning.\n"]
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("id"))
+    }
+
+    #[doc = "Get a reference to the value of field `nt` after provisioning.\n"]
+    pub fn nt(&self) -> ListRef<DeepNtElRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("nt"))
+    }
+
+    #[doc = "Get a reference to the value of field `cblock` after provisioning.\n"]
+    pub fn cblock(&self) -> ListRef<DeepCblockElRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("cblock"))
+    }
+
+    #[doc = "Get a reference to the value of field `sblock` after provisioning.\n"]
+    pub fn sblock(&self) -> SetRef<DeepSblockElRef> {
+        SetRef::new(self.shared().clone(), self.attr_ref("sblock"))
+    }
+}
+
+impl Referable for Deep {
+    fn extract_ref(&self) -> String {
+        format!("{}.{}", self.0.extract_resource_type(), self.0.extract_tf_id())
+    }
+}
+
+impl Resource for Deep { }
+
+impl std::fmt::Debug for Deep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(stringify!(Deep)).field("type", &"fake_deep").field("tf_id", &self.0.tf_id).finish()
+    }
+}
+
+impl ToListMappable for Deep {
+    type O = ListRef<DeepRef>;
+
+    fn do_map(self, base: String) -> Self::O {
+        self.0.data.borrow_mut().for_each = Some(format!("${{{}}}", base));
+        ListRef::new(self.0.shared.clone(), self.extract_ref())
+    }
+}
+
+impl Resource_ for Deep_ {
+    fn extract_resource_type(&self) -> String {
+        "fake_deep".into()
+    }
+
+    fn extract_tf_id(&self) -> String {
+        self.tf_id.clone()
+    }
+
+    fn extract_value(&self) -> serde_json::Value {
+        serde_json::to_value(&self.data).unwrap()
+    }
+}
+
+pub struct BuildDeep {
+    pub tf_id: String,
+}
+
+impl BuildDeep {
+    fn create(self, stack: &Stack) -> Deep {
+        Deep(Rc::new(Deep_ {
+            shared: stack.shared.clone(),
+            tf_id: self.tf_id,
+            data: RefCell::new(DeepData {
+                depends_on: core::default::Default::default(),
+                provider: None,
+                lifecycle: core::default::Default::default(),
+                for_each: None,
+                count: None,
+                cblock: core::default::Default::default(),
+                sblock: core::default::Default::default(),
+                dynamic: Default::default(),
+                __terrars_raw: Default::default(),
+            }),
+        }))
+    }
+
+    pub fn build(self, stack: &mut Stack) -> Deep {
+        let out = self.create(stack);
+        stack.add_resource(out.0.clone());
+        out
+    }
+
+    #[doc =
+        "Like `build` but fails instead of adding the resource if `tf_id` isn't a valid Terraform identifier or is already used by another resource of the same type."]
+    pub fn try_build(self, stack: &mut Stack) -> Result<Deep, StackError> {
+        let out = self.create(stack);
+        stack.try_add_resource(out.0.clone())?;
+        Ok(out)
+    }
+}
+
+pub struct DeepRef {
+    shared: StackShared,
+    base: String,
+    guarded: bool,
+}
+
+impl Ref for DeepRef {
+    fn new(shared: StackShared, base: String) -> Self {
+        Self {
+            shared: shared,
+            base: base,
+            guarded: false,
+        }
+    }
+}
+
+impl DeepRef {
+    #[doc =
+        "Create a reference to an existing resource by its address (ex: `type.name`), for instance one managed outside this stack."]
+    pub fn from_address(shared: StackShared, address: &str) -> Self {
+        Self::new(shared, address.to_string())
+    }
+
+    #[doc = "Refer to one instance of a resource created with `count`."]
+    pub fn index(&self, i: usize) -> Self {
+        Self::new(self.shared.clone(), format!("{}[{}]", self.base, i))
+    }
+
+    #[doc = "Refer to one instance of a resource created with `for_each` by its key."]
+    pub fn instance(&self, key: &str) -> Self {
+        Self::new(self.shared.clone(), format!("{}[{}]", self.base, key.to_string().to_expr_raw()))
+    }
+
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    fn attr_ref(&self, attr: &str) -> String {
+        if self.guarded {
+            format!("one({}[*].{})", self.base, attr)
+        } else {
+            format!("{}.{}", self.base, attr)
+        }
+    }
+
+    #[doc = "Get a reference to the value of field `deep` after provisioning.\n"]
+    pub fn deep(&self) -> ListRef<DeepDeepElRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("deep"))
+    }
+
+    #[doc = "Get a reference to the value of field `id` after provisioning.\n"]
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("id"))
+    }
+
+    #[doc = "Get a reference to the value of field `nt` after provisioning.\n"]
+    pub fn nt(&self) -> ListRef<DeepNtElRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("nt"))
+    }
+
+    #[doc = "Get a reference to the value of field `cblock` after provisioning.\n"]
+    pub fn cblock(&self) -> ListRef<DeepCblockElRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("cblock"))
+    }
+
+    #[doc = "Get a reference to the value of field `sblock` after provisioning.\n"]
+    pub fn sblock(&self) -> SetRef<DeepSblockElRef> {
+        SetRef::new(self.shared().clone(), self.attr_ref("sblock"))
+    }
+}
+
+impl Referable for DeepRef {
+    fn extract_ref(&self) -> String {
+        self.base.clone()
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeepDeepElInnerEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<PrimField<String>>,
+}
+
+impl DeepDeepElInnerEl {
+    #[doc = "Set the field `value`.\n"]
+    #[must_use]
+    pub fn set_value(mut self, v: impl Into<PrimField<String>>) -> Self {
+        self.value = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `value` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_value_opt(mut self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.value = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for DeepDeepElInnerEl {
+    type O = BlockAssignable<DeepDeepElInnerEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildDeepDeepElInnerEl {}
+
+impl BuildDeepDeepElInnerEl {
+    pub fn build(self) -> DeepDeepElInnerEl {
+        DeepDeepElInnerEl { value: core::default::Default::default() }
+    }
+}
+
+pub struct DeepDeepElInnerElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for DeepDeepElInnerElRef {
+    fn new(shared: StackShared, base: String) -> DeepDeepElInnerElRef {
+        DeepDeepElInnerElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl DeepDeepElInnerElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `value` after provisioning.\n"]
+    pub fn value(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.value", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeepDeepElMEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    q: Option<PrimField<f64>>,
+}
+
+impl DeepDeepElMEl {
+    #[doc = "Set the field `q`.\n"]
+    #[must_use]
+    pub fn set_q(mut self, v: impl Into<PrimField<f64>>) -> Self {
+        self.q = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `q` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_q_opt(mut self, v: Option<PrimField<f64>>) -> Self {
+        if let Some(v) = v {
+            self.q = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for DeepDeepElMEl {
+    type O = BlockAssignable<DeepDeepElMEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildDeepDeepElMEl {}
+
+impl BuildDeepDeepElMEl {
+    pub fn build(self) -> DeepDeepElMEl {
+        DeepDeepElMEl { q: core::default::Default::default() }
+    }
+}
+
+pub struct DeepDeepElMElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for DeepDeepElMElRef {
+    fn new(shared: StackShared, base: String) -> DeepDeepElMElRef {
+        DeepDeepElMElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl DeepDeepElMElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `q` after provisioning.\n"]
+    pub fn q(&self) -> PrimExpr<f64> {
+        PrimExpr::new(self.shared().clone(), format!("{}.q", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeepDeepEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inner: Option<ListField<DeepDeepElInnerEl>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    m: Option<RecField<DeepDeepElMEl>>,
+}
+
+impl DeepDeepEl {
+    #[doc = "Set the field `inner`.\n"]
+    #[must_use]
+    pub fn set_inner(mut self, v: impl Into<ListField<DeepDeepElInnerEl>>) -> Self {
+        self.inner = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `inner` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_inner_opt(mut self, v: Option<ListField<DeepDeepElInnerEl>>) -> Self {
+        if let Some(v) = v {
+            self.inner = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `m`.\n"]
+    #[must_use]
+    pub fn set_m(mut self, v: impl Into<RecField<DeepDeepElMEl>>) -> Self {
+        self.m = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `m` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_m_opt(mut self, v: Option<RecField<DeepDeepElMEl>>) -> Self {
+        if let Some(v) = v {
+            self.m = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for DeepDeepEl {
+    type O = BlockAssignable<DeepDeepEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildDeepDeepEl {}
+
+impl BuildDeepDeepEl {
+    pub fn build(self) -> DeepDeepEl {
+        DeepDeepEl {
+            inner: core::default::Default::default(),
+            m: core::default::Default::default(),
+        }
+    }
+}
+
+pub struct DeepDeepElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for DeepDeepElRef {
+    fn new(shared: StackShared, base: String) -> DeepDeepElRef {
+        DeepDeepElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl DeepDeepElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `inner` after provisioning.\n"]
+    pub fn inner(&self) -> ListRef<DeepDeepElInnerElRef> {
+        ListRef::new(self.shared().clone(), format!("{}.inner", self.base))
+    }
+
+    #[doc = "Get a reference to the value of field `m` after provisioning.\n"]
+    pub fn m(&self) -> RecRef<DeepDeepElMElRef> {
+        RecRef::new(self.shared().clone(), format!("{}.m", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeepNtElInnerEl {}
+
+impl DeepNtElInnerEl { }
+
+impl ToListMappable for DeepNtElInnerEl {
+    type O = BlockAssignable<DeepNtElInnerEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildDeepNtElInnerEl {}
+
+impl BuildDeepNtElInnerEl {
+    pub fn build(self) -> DeepNtElInnerEl {
+        DeepNtElInnerEl {}
+    }
+}
+
+pub struct DeepNtElInnerElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for DeepNtElInnerElRef {
+    fn new(shared: StackShared, base: String) -> DeepNtElInnerElRef {
+        DeepNtElInnerElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl DeepNtElInnerElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `value` after provisioning.\n"]
+    pub fn value(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.value", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeepNtEl {}
+
+impl DeepNtEl { }
+
+impl ToListMappable for DeepNtEl {
+    type O = BlockAssignable<DeepNtEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildDeepNtEl {}
+
+impl BuildDeepNtEl {
+    pub fn build(self) -> DeepNtEl {
+        DeepNtEl {}
+    }
+}
+
+pub struct DeepNtElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for DeepNtElRef {
+    fn new(shared: StackShared, base: String) -> DeepNtElRef {
+        DeepNtElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl DeepNtElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `inner` after provisioning.\n"]
+    pub fn inner(&self) -> ListRef<DeepNtElInnerElRef> {
+        ListRef::new(self.shared().clone(), format!("{}.inner", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeepCblockElSubEl {}
+
+impl DeepCblockElSubEl { }
+
+impl ToListMappable for DeepCblockElSubEl {
+    type O = BlockAssignable<DeepCblockElSubEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildDeepCblockElSubEl {}
+
+impl BuildDeepCblockElSubEl {
+    pub fn build(self) -> DeepCblockElSubEl {
+        DeepCblockElSubEl {}
+    }
+}
+
+pub struct DeepCblockElSubElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for DeepCblockElSubElRef {
+    fn new(shared: StackShared, base: String) -> DeepCblockElSubElRef {
+        DeepCblockElSubElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl DeepCblockElSubElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `value` after provisioning.\n"]
+    pub fn value(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.value", self.base))
+    }
+}
+
+#[derive(Serialize, Default)]
+struct DeepCblockElDynamic {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub: Option<DynamicBlock<DeepCblockElSubEl>>,
+}
+
+impl DeepCblockElDynamic {
+    fn is_empty(&self) -> bool {
+        self.sub.is_none()
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeepCblockEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub: Option<Vec<DeepCblockElSubEl>>,
+    #[serde(skip_serializing_if = "DeepCblockElDynamic::is_empty")]
+    dynamic: DeepCblockElDynamic,
+}
+
+impl DeepCblockEl {
+    #[doc = "Set the field `sub`.\n"]
+    #[must_use]
+    pub fn set_sub(mut self, v: impl Into<BlockAssignable<DeepCblockElSubEl>>) -> Self {
+        match v.into() {
+            BlockAssignable::Literal(v) => {
+                self.sub = Some(v);
+            },
+            BlockAssignable::Dynamic(d) => {
+                self.dynamic.sub = Some(d);
+            },
+        }
+        self
+    }
+}
+
+impl ToListMappable for DeepCblockEl {
+    type O = BlockAssignable<DeepCblockEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildDeepCblockEl {}
+
+impl BuildDeepCblockEl {
+    pub fn build(self) -> DeepCblockEl {
+        DeepCblockEl {
+            sub: core::default::Default::default(),
+            dynamic: Default::default(),
+        }
+    }
+}
+
+pub struct DeepCblockElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for DeepCblockElRef {
+    fn new(shared: StackShared, base: String) -> DeepCblockElRef {
+        DeepCblockElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl DeepCblockElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `v` after provisioning.\n"]
+    pub fn v(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.v", self.base))
+    }
+
+    #[doc = "Get a reference to the value of field `sub` after provisioning.\n"]
+    pub fn sub(&self) -> ListRef<DeepCblockElSubElRef> {
+        ListRef::new(self.shared().clone(), format!("{}.sub", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeepSblockElSubEl {}
+
+impl DeepSblockElSubEl { }
+
+impl ToListMappable for DeepSblockElSubEl {
+    type O = BlockAssignable<DeepSblockElSubEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildDeepSblockElSubEl {}
+
+impl BuildDeepSblockElSubEl {
+    pub fn build(self) -> DeepSblockElSubEl {
+        DeepSblockElSubEl {}
+    }
+}
+
+pub struct DeepSblockElSubElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for DeepSblockElSubElRef {
+    fn new(shared: StackShared, base: String) -> DeepSblockElSubElRef {
+        DeepSblockElSubElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl DeepSblockElSubElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `value` after provisioning.\n"]
+    pub fn value(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.value", self.base))
+    }
+}
+
+#[derive(Serialize, Default)]
+struct DeepSblockElDynamic {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub: Option<DynamicBlock<DeepSblockElSubEl>>,
+}
+
+impl DeepSblockElDynamic {
+    fn is_empty(&self) -> bool {
+        self.sub.is_none()
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeepSblockEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    v: Option<PrimField<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub: Option<Vec<DeepSblockElSubEl>>,
+    #[serde(skip_serializing_if = "DeepSblockElDynamic::is_empty")]
+    dynamic: DeepSblockElDynamic,
+}
+
+impl DeepSblockEl {
+    #[doc = "Set the field `v`.\n"]
+    #[must_use]
+    pub fn set_v(mut self, v: impl Into<PrimField<String>>) -> Self {
+        self.v = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `v` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_v_opt(mut self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.v = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `sub`.\n"]
+    #[must_use]
+    pub fn set_sub(mut self, v: impl Into<BlockAssignable<DeepSblockElSubEl>>) -> Self {
+        match v.into() {
+            BlockAssignable::Literal(v) => {
+                self.sub = Some(v);
+            },
+            BlockAssignable::Dynamic(d) => {
+                self.dynamic.sub = Some(d);
+            },
+        }
+        self
+    }
+}
+
+impl ToListMappable for DeepSblockEl {
+    type O = BlockAssignable<DeepSblockEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildDeepSblockEl {}
+
+impl BuildDeepSblockEl {
+    pub fn build(self) -> DeepSblockEl {
+        DeepSblockEl {
+            v: core::default::Default::default(),
+            sub: core::default::Default::default(),
+            dynamic: Default::default(),
+        }
+    }
+}
+
+pub struct DeepSblockElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for DeepSblockElRef {
+    fn new(shared: StackShared, base: String) -> DeepSblockElRef {
+        DeepSblockElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl DeepSblockElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `v` after provisioning.\n"]
+    pub fn v(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.v", self.base))
+    }
+
+    #[doc = "Get a reference to the value of field `sub` after provisioning.\n"]
+    pub fn sub(&self) -> SetRef<DeepSblockElSubElRef> {
+        SetRef::new(self.shared().clone(), format!("{}.sub", self.base))
+    }
+}
+
+#[derive(Serialize, Default)]
+struct DeepDynamic {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cblock: Option<DynamicBlock<DeepCblockEl>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sblock: Option<DynamicBlock<DeepSblockEl>>,
+}
+
+impl DeepDynamic {
+    fn is_empty(&self) -> bool {
+        self.cblock.is_none() && self.sblock.is_none()
+    }
+}
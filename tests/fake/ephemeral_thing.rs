@@ -0,0 +1,252 @@
+use serde::Serialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+use terrars::*;
+use super::provider::ProviderFake;
+
+#[derive(Serialize)]
+struct EphemeralThingData {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+    #[serde(skip_serializing_if = "SerdeSkipDefault::is_default")]
+    provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    for_each: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<PrimField<i64>>,
+    id: PrimField<String>,
+    #[serde(flatten)]
+    __terrars_raw: std::collections::BTreeMap<String, AnyField>,
+}
+
+struct EphemeralThing_ {
+    shared: StackShared,
+    tf_id: String,
+    data: RefCell<EphemeralThingData>,
+}
+
+#[derive(Clone)]
+pub struct EphemeralThing(Rc<EphemeralThing_>);
+
+impl EphemeralThing {
+    fn shared(&self) -> &StackShared {
+        &self.0.shared
+    }
+
+    fn attr_ref(&self, attr: &str) -> String {
+        format!("{}.{}", self.extract_ref(), attr)
+    }
+
+    pub fn depends_on(self, dep: &impl Referable) -> Self {
+        self.0.data.borrow_mut().depends_on.push(dep.extract_ref());
+        self
+    }
+
+    pub fn set_provider(&self, provider: &ProviderFake) -> &Self {
+        self.0.data.borrow_mut().provider = Some(provider.provider_ref());
+        self
+    }
+
+    #[doc =
+        "Open `v` instances of the ephemeral resource (`count`). Use `index` to refer to an instance. Can't be combined with `for_each` (ex: creating the ephemeral resource with `map`)."]
+    pub fn set_count(self, v: impl Into<PrimField<i64>>) -> Self {
+        self.0.data.borrow_mut().count = Some(v.into());
+        self
+    }
+
+    #[doc =
+        "Only open the ephemeral resource if `cond` is true (sets `count` to 1 or 0). Use `enabled_ref` to refer to the ephemeral resource. Can't be combined with `for_each` (ex: creating the ephemeral resource with `map`)."]
+    pub fn set_enabled_if(self, cond: &impl Expr<bool>) -> Self {
+        let count = self.shared().add_sentinel(&format!("{} ? 1 : 0", cond.expr_raw().1));
+        self.0.data.borrow_mut().count = Some(PrimField::Sentinel(count));
+        self
+    }
+
+    #[doc =
+        "Refer to the ephemeral resource when it was made conditional with `set_enabled_if`. Attributes are `null` when the ephemeral resource is disabled. Values nested in blocks aren't guarded, so only refer to them when the condition is true."]
+    pub fn enabled_ref(&self) -> EphemeralThingRef {
+        EphemeralThingRef {
+            shared: self.shared().clone(),
+            base: self.extract_ref(),
+            guarded: true,
+        }
+    }
+
+    #[doc = "Refer to one instance of an ephemeral resource opened with `set_count`."]
+    pub fn index(&self, i: usize) -> EphemeralThingRef {
+        EphemeralThingRef::new(self.shared().clone(), format!("{}[{}]", self.extract_ref(), i))
+    }
+
+    #[doc =
+        "Refer to one instance of an ephemeral resource opened with `for_each` (ex: with `map`) by its key. Use `extract_ref` on the result to get the address for `RunOptions::targets` or `RunOptions::replace`."]
+    pub fn instance(&self, key: &str) -> EphemeralThingRef {
+        EphemeralThingRef::new(
+            self.shared().clone(),
+            format!("{}[{}]", self.extract_ref(), key.to_string().to_expr_raw()),
+        )
+    }
+
+    #[doc =
+        "Set attributes and blocks from a value that serializes to a json object in Terraform's json syntax (ex: the ephemeral resource body from an existing `.tf.json` file). This bypasses type checking and takes precedence over values set with the typed setters. Strings are escaped like other literal strings, but may contain expressions converted to strings (ex: with `to_string`). Panics if the value doesn't serialize to an object."]
+    pub fn set_from(self, v: impl Serialize) -> Self {
+        match serde_json::to_value(v).unwrap() {
+            serde_json::Value::Object(o) => self
+                .0
+                .data
+                .borrow_mut()
+                .__terrars_raw
+                .extend(o.into_iter().map(|(k, v)| (k, AnyField::Literal(v)))),
+            _ => panic!("set_from value must serialize to a json object"),
+        }
+        self
+    }
+
+    #[doc = "Get a reference to the value of field `id` after provisioning.\n"]
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("id"))
+    }
+
+    #[doc = "Get a reference to the value of field `out` after provisioning.\n"]
+    pub fn out(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("out"))
+    }
+}
+
+impl Referable for EphemeralThing {
+    fn extract_ref(&self) -> String {
+        format!("ephemeral.{}.{}", self.0.extract_ephemeral_type(), self.0.extract_tf_id())
+    }
+}
+
+impl Ephemeral for EphemeralThing { }
+
+impl std::fmt::Debug for EphemeralThing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f
+            .debug_struct(stringify!(EphemeralThing))
+            .field("type", &"fake_thing")
+            .field("tf_id", &self.0.tf_id)
+            .finish()
+    }
+}
+
+impl ToListMappable for EphemeralThing {
+    type O = ListRef<EphemeralThingRef>;
+
+    fn do_map(self, base: String) -> Self::O {
+        self.0.data.borrow_mut().for_each = Some(format!("${{{}}}", base));
+        ListRef::new(self.0.shared.clone(), self.extract_ref())
+    }
+}
+
+impl Ephemeral_ for EphemeralThing_ {
+    fn extract_ephemeral_type(&self) -> String {
+        "fake_thing".into()
+    }
+
+    fn extract_tf_id(&self) -> String {
+        self.tf_id.clone()
+    }
+
+    fn extract_value(&self) -> serde_json::Value {
+        serde_json::to_value(&self.data).unwrap()
+    }
+}
+
+pub struct BuildEphemeralThing {
+    pub tf_id: String,
+    pub id: PrimField<String>,
+}
+
+impl BuildEphemeralThing {
+    fn create(self, stack: &Stack) -> EphemeralThing {
+        EphemeralThing(Rc::new(EphemeralThing_ {
+            shared: stack.shared.clone(),
+            tf_id: self.tf_id,
+            data: RefCell::new(EphemeralThingData {
+                depends_on: core::default::Default::default(),
+                provider: None,
+                for_each: None,
+                count: None,
+                id: self.id,
+                __terrars_raw: Default::default(),
+            }),
+        }))
+    }
+
+    pub fn build(self, stack: &mut Stack) -> EphemeralThing {
+        let out = self.create(stack);
+        stack.add_ephemeral(out.0.clone());
+        out
+    }
+
+    #[doc =
+        "Like `build` but fails instead of adding the ephemeral resource if `tf_id` isn't a valid Terraform identifier or is already used by another ephemeral resource of the same type."]
+    pub fn try_build(self, stack: &mut Stack) -> Result<EphemeralThing, StackError> {
+        let out = self.create(stack);
+        stack.try_add_ephemeral(out.0.clone())?;
+        Ok(out)
+    }
+}
+
+pub struct EphemeralThingRef {
+    shared: StackShared,
+    base: String,
+    guarded: bool,
+}
+
+impl Ref for EphemeralThingRef {
+    fn new(shared: StackShared, base: String) -> Self {
+        Self {
+            shared: shared,
+            base: base,
+            guarded: false,
+        }
+    }
+}
+
+impl EphemeralThingRef {
+    #[doc =
+        "Create a reference to an existing ephemeral resource by its address (ex: `ephemeral.type.name`), for instance one defined outside this stack."]
+    pub fn from_address(shared: StackShared, address: &str) -> Self {
+        Self::new(shared, address.to_string())
+    }
+
+    #[doc = "Refer to one instance of an ephemeral resource opened with `count`."]
+    pub fn index(&self, i: usize) -> Self {
+        Self::new(self.shared.clone(), format!("{}[{}]", self.base, i))
+    }
+
+    #[doc = "Refer to one instance of an ephemeral resource opened with `for_each` by its key."]
+    pub fn instance(&self, key: &str) -> Self {
+        Self::new(self.shared.clone(), format!("{}[{}]", self.base, key.to_string().to_expr_raw()))
+    }
+
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    fn attr_ref(&self, attr: &str) -> String {
+        if self.guarded {
+            format!("one({}[*].{})", self.base, attr)
+        } else {
+            format!("{}.{}", self.base, attr)
+        }
+    }
+
+    #[doc = "Get a reference to the value of field `id` after provisioning.\n"]
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("id"))
+    }
+
+    #[doc = "Get a reference to the value of field `out` after provisioning.\n"]
+    pub fn out(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("out"))
+    }
+}
+
+impl Referable for EphemeralThingRef {
+    fn extract_ref(&self) -> String {
+        self.base.clone()
+    }
+}
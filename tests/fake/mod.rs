@@ -0,0 +1,31 @@
+pub mod provider;
+
+pub use provider::*;
+
+pub mod deep;
+
+pub use deep::*;
+
+pub mod mod_;
+
+pub use mod_::*;
+
+pub mod provider_;
+
+pub use provider_::*;
+
+pub mod thing;
+
+pub use thing::*;
+
+pub mod type_;
+
+pub use type_::*;
+
+pub mod data_thing;
+
+pub use data_thing::*;
+
+pub mod ephemeral_thing;
+
+pub use ephemeral_thing::*;
@@ -0,0 +1,202 @@
+use serde::Serialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+use terrars::*;
+
+#[derive(Serialize)]
+struct ProviderFakeData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alias: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<PrimField<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    endpoints: Option<RecField<FakeEndpointsEl>>,
+    #[serde(flatten)]
+    __terrars_raw: std::collections::BTreeMap<String, AnyField>,
+}
+
+struct ProviderFake_ {
+    data: RefCell<ProviderFakeData>,
+}
+
+pub struct ProviderFake(Rc<ProviderFake_>);
+
+impl ProviderFake {
+    pub fn provider_ref(&self) -> String {
+        let data = self.0.data.borrow();
+        if let Some(alias) = &data.alias {
+            format!("{}.{}", "fake", alias)
+        } else {
+            "fake".into()
+        }
+    }
+
+    pub fn set_alias(self, alias: impl ToString) -> Self {
+        self.0.data.borrow_mut().alias = Some(alias.to_string());
+        self
+    }
+
+    #[doc =
+        "Set an arbitrary attribute or block, for settings missing from the schema the bindings were generated from. This bypasses type checking and replaces any value set with the typed setters."]
+    pub fn set_raw_block(self, k: impl ToString, v: impl Into<AnyField>) -> Self {
+        self.0.data.borrow_mut().__terrars_raw.insert(k.to_string(), v.into());
+        self
+    }
+
+    #[doc = "Set the field `region`.\nRegion"]
+    pub fn set_region(self, v: impl Into<PrimField<String>>) -> Self {
+        self.0.data.borrow_mut().region = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `region` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_region_opt(self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().region = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `endpoints`.\n"]
+    pub fn set_endpoints(self, v: impl Into<RecField<FakeEndpointsEl>>) -> Self {
+        self.0.data.borrow_mut().endpoints = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `endpoints` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_endpoints_opt(self, v: Option<RecField<FakeEndpointsEl>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().endpoints = Some(v);
+        }
+        self
+    }
+}
+
+impl Provider for ProviderFake_ {
+    fn extract_type_tf_id(&self) -> String {
+        "fake".into()
+    }
+
+    fn extract_provider_type(&self) -> serde_json::Value {
+        serde_json::json!({
+            "source": "hashicorp/fake",
+            "version": "1.0.0",
+        })
+    }
+
+    fn extract_schema_version(&self) -> Option<String> {
+        Some("1.0.0".into())
+    }
+
+    fn extract_provider(&self) -> serde_json::Value {
+        serde_json::to_value(&self.data).unwrap()
+    }
+}
+
+pub struct BuildProviderFake {}
+
+impl BuildProviderFake {
+    pub fn build(self, stack: &mut Stack) -> ProviderFake {
+        let out = ProviderFake(Rc::new(ProviderFake_ { data: RefCell::new(ProviderFakeData {
+            alias: None,
+            region: core::default::Default::default(),
+            endpoints: core::default::Default::default(),
+            __terrars_raw: Default::default(),
+        }) }));
+        stack.add_provider(out.0.clone());
+        out
+    }
+}
+
+#[doc = "Allowed values for `provider.region`, from the generation config."]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FakeRegion {
+    UsEast1,
+    EuWest1,
+    V1st,
+}
+
+impl FakeRegion {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FakeRegion::UsEast1 => "us-east-1",
+            FakeRegion::EuWest1 => "eu-west-1",
+            FakeRegion::V1st => "1st",
+        }
+    }
+}
+
+impl From<FakeRegion> for PrimField<String> {
+    fn from(v: FakeRegion) -> Self {
+        v.as_str().into()
+    }
+}
+
+#[derive(Serialize)]
+pub struct FakeEndpointsEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<PrimField<String>>,
+}
+
+impl FakeEndpointsEl {
+    #[doc = "Set the field `url`.\n"]
+    #[must_use]
+    pub fn set_url(mut self, v: impl Into<PrimField<String>>) -> Self {
+        self.url = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `url` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_url_opt(mut self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.url = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for FakeEndpointsEl {
+    type O = BlockAssignable<FakeEndpointsEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildFakeEndpointsEl {}
+
+impl BuildFakeEndpointsEl {
+    pub fn build(self) -> FakeEndpointsEl {
+        FakeEndpointsEl { url: core::default::Default::default() }
+    }
+}
+
+pub struct FakeEndpointsElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for FakeEndpointsElRef {
+    fn new(shared: StackShared, base: String) -> FakeEndpointsElRef {
+        FakeEndpointsElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl FakeEndpointsElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `url` after provisioning.\n"]
+    pub fn url(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.url", self.base))
+    }
+}
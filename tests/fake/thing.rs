@@ -0,0 +1,1435 @@
+use serde::Serialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+use terrars::*;
+use super::provider::ProviderFake;
+
+#[derive(Serialize)]
+struct ThingData {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<String>,
+    #[serde(skip_serializing_if = "SerdeSkipDefault::is_default")]
+    lifecycle: ResourceLifecycle,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    for_each: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<PrimField<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    any_list: Option<ListField<AnyField>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    anything: Option<AnyField>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    list_map: Option<RecField<ListField<PrimField<String>>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    listed: Option<Vec<ThingListedEl>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mapped: Option<RecField<ThingMappedEl>>,
+    name: PrimField<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_map: Option<RecField<PrimField<f64>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    obj_map: Option<RecField<ThingObjMapEl>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password_wo: Option<PrimField<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password_wo_version: Option<PrimField<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raw: Option<PrimField<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    setted: Option<Vec<ThingSettedEl>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    single: Option<ThingSingle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags_all: Option<RecField<PrimField<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bgroup: Option<ThingBgroupEl>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blist: Option<Vec<ThingBlistEl>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bmap: Option<RecField<ThingBmapEl>>,
+    #[serde(skip_serializing_if = "ThingDynamic::is_empty")]
+    dynamic: ThingDynamic,
+    #[serde(flatten)]
+    __terrars_raw: std::collections::BTreeMap<String, AnyField>,
+}
+
+struct Thing_ {
+    shared: StackShared,
+    tf_id: String,
+    data: RefCell<ThingData>,
+}
+
+#[derive(Clone)]
+pub struct Thing(Rc<Thing_>);
+
+impl Thing {
+    fn shared(&self) -> &StackShared {
+        &self.0.shared
+    }
+
+    fn attr_ref(&self, attr: &str) -> String {
+        format!("{}.{}", self.extract_ref(), attr)
+    }
+
+    pub fn depends_on(self, dep: &impl Referable) -> Self {
+        self.0.data.borrow_mut().depends_on.push(dep.extract_ref());
+        self
+    }
+
+    pub fn set_provider(self, provider: &ProviderFake) -> Self {
+        self.0.data.borrow_mut().provider = Some(provider.provider_ref());
+        self
+    }
+
+    #[doc =
+        "Create `v` instances of the resource (`count`). Use `index` to refer to an instance. Can't be combined with `for_each` (ex: creating the resource with `map`)."]
+    pub fn set_count(self, v: impl Into<PrimField<i64>>) -> Self {
+        self.0.data.borrow_mut().count = Some(v.into());
+        self
+    }
+
+    #[doc =
+        "Only create the resource if `cond` is true (sets `count` to 1 or 0). Use `enabled_ref` to refer to the resource. Can't be combined with `for_each` (ex: creating the resource with `map`)."]
+    pub fn set_enabled_if(self, cond: &impl Expr<bool>) -> Self {
+        let count = self.shared().add_sentinel(&format!("{} ? 1 : 0", cond.expr_raw().1));
+        self.0.data.borrow_mut().count = Some(PrimField::Sentinel(count));
+        self
+    }
+
+    #[doc =
+        "Refer to the resource when it was made conditional with `set_enabled_if`. Attributes are `null` when the resource is disabled. Values nested in blocks aren't guarded, so only refer to them when the condition is true."]
+    pub fn enabled_ref(&self) -> ThingRef {
+        ThingRef {
+            shared: self.shared().clone(),
+            base: self.extract_ref(),
+            guarded: true,
+        }
+    }
+
+    #[doc = "Refer to one instance of a resource created with `set_count`."]
+    pub fn index(&self, i: usize) -> ThingRef {
+        ThingRef::new(self.shared().clone(), format!("{}[{}]", self.extract_ref(), i))
+    }
+
+    #[doc =
+        "Refer to one instance of a resource created with `for_each` (ex: with `map`) by its key. Use `extract_ref` on the result to get the address for `RunOptions::targets` or `RunOptions::replace`."]
+    pub fn instance(&self, key: &str) -> ThingRef {
+        ThingRef::new(self.shared().clone(), format!("{}[{}]", self.extract_ref(), key.to_string().to_expr_raw()))
+    }
+
+    pub fn set_create_before_destroy(self, v: bool) -> Self {
+        self.0.data.borrow_mut().lifecycle.create_before_destroy = v;
+        self
+    }
+
+    pub fn set_prevent_destroy(self, v: bool) -> Self {
+        self.0.data.borrow_mut().lifecycle.prevent_destroy = v;
+        self
+    }
+
+    pub fn ignore_changes_to_all(self) -> Self {
+        self.0.data.borrow_mut().lifecycle.ignore_changes = Some(IgnoreChanges::All(IgnoreChangesAll::All));
+        self
+    }
+
+    pub fn ignore_changes_to_attr(self, attr: impl ToString) -> Self {
+        {
+            let mut d = self.0.data.borrow_mut();
+            if match &mut d.lifecycle.ignore_changes {
+                Some(i) => match i {
+                    IgnoreChanges::All(_) => {
+                        true
+                    },
+                    IgnoreChanges::Refs(r) => {
+                        r.push(attr.to_string());
+                        false
+                    },
+                },
+                None => true,
+            } {
+                d.lifecycle.ignore_changes = Some(IgnoreChanges::Refs(vec![attr.to_string()]));
+            }
+        }
+        self
+    }
+
+    pub fn replace_triggered_by_resource(self, r: &impl Resource) -> Self {
+        self.0.data.borrow_mut().lifecycle.replace_triggered_by.push(r.extract_ref());
+        self
+    }
+
+    pub fn replace_triggered_by_attr(self, attr: impl ToString) -> Self {
+        self.0.data.borrow_mut().lifecycle.replace_triggered_by.push(attr.to_string());
+        self
+    }
+
+    #[doc = "Remove all attributes set with `ignore_changes_to_attr` or `ignore_changes_to_all`."]
+    pub fn clear_ignore_changes(self) -> Self {
+        self.0.data.borrow_mut().lifecycle.ignore_changes = None;
+        self
+    }
+
+    #[doc = "Remove all references added with `replace_triggered_by_resource` or `replace_triggered_by_attr`."]
+    pub fn clear_replace_triggered_by(self) -> Self {
+        self.0.data.borrow_mut().lifecycle.replace_triggered_by.clear();
+        self
+    }
+
+    #[doc = "Reset all `lifecycle` settings to their defaults."]
+    pub fn clear_lifecycle(self) -> Self {
+        self.0.data.borrow_mut().lifecycle = Default::default();
+        self
+    }
+
+    #[doc =
+        "Merge a value that serializes to a json object into the `lifecycle` block (ex: arguments added in newer Terraform versions). This bypasses the typed lifecycle setters and takes precedence over them. Panics if the value doesn't serialize to an object."]
+    pub fn set_lifecycle_raw(self, v: impl Serialize) -> Self {
+        match serde_json::to_value(v).unwrap() {
+            serde_json::Value::Object(o) => self.0.data.borrow_mut().lifecycle.raw.extend(o),
+            _ => panic!("set_lifecycle_raw value must serialize to a json object"),
+        }
+        self
+    }
+
+    #[doc =
+        "Set attributes and blocks from a value that serializes to a json object in Terraform's json syntax (ex: the resource body from an existing `.tf.json` file). This bypasses type checking and takes precedence over values set with the typed setters. Strings are escaped like other literal strings, but may contain expressions converted to strings (ex: with `to_string`). Panics if the value doesn't serialize to an object."]
+    pub fn set_from(self, v: impl Serialize) -> Self {
+        match serde_json::to_value(v).unwrap() {
+            serde_json::Value::Object(o) => self
+                .0
+                .data
+                .borrow_mut()
+                .__terrars_raw
+                .extend(o.into_iter().map(|(k, v)| (k, AnyField::Literal(v)))),
+            _ => panic!("set_from value must serialize to a json object"),
+        }
+        self
+    }
+
+    #[doc =
+        "Set a single attribute or block in Terraform's json syntax, for last-minute overrides (ex: with a `serde_json::Value` or an expression). Like `set_from`, this bypasses type checking and takes precedence over values set with the typed setters."]
+    pub fn set_data_raw(&self, k: impl ToString, v: impl Into<AnyField>) {
+        self.0.data.borrow_mut().__terrars_raw.insert(k.to_string(), v.into());
+    }
+
+    #[doc =
+        "Get the current value of an attribute or block as it will be serialized, or `None` if it isn't set. Expressions appear as `_TERRARS_SENTINEL*` placeholders."]
+    pub fn get_data_raw(&self, k: &str) -> Option<serde_json::Value> {
+        match serde_json::to_value(&self.0.data).unwrap() {
+            serde_json::Value::Object(mut o) => o.remove(k),
+            _ => None,
+        }
+    }
+
+    #[doc = "Set the field `any_list`.\n"]
+    pub fn set_any_list(self, v: impl Into<ListField<AnyField>>) -> Self {
+        self.0.data.borrow_mut().any_list = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `any_list` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_any_list_opt(self, v: Option<ListField<AnyField>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().any_list = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `anything`.\n"]
+    pub fn set_anything(self, v: impl Into<AnyField>) -> Self {
+        self.0.data.borrow_mut().anything = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `anything` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_anything_opt(self, v: Option<AnyField>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().anything = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `list_map`.\n"]
+    pub fn set_list_map(self, v: impl Into<RecField<ListField<PrimField<String>>>>) -> Self {
+        self.0.data.borrow_mut().list_map = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `list_map` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_list_map_opt(self, v: Option<RecField<ListField<PrimField<String>>>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().list_map = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `listed`.\n"]
+    pub fn set_listed(self, v: impl Into<Vec<ThingListedEl>>) -> Self {
+        self.0.data.borrow_mut().listed = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `listed` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_listed_opt(self, v: Option<Vec<ThingListedEl>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().listed = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `mapped`.\n"]
+    pub fn set_mapped(self, v: impl Into<RecField<ThingMappedEl>>) -> Self {
+        self.0.data.borrow_mut().mapped = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `mapped` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_mapped_opt(self, v: Option<RecField<ThingMappedEl>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().mapped = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `name` to the JSON serialization of `v`."]
+    pub fn set_name_json(self, v: &impl Serialize) -> Self {
+        self.0.data.borrow_mut().name = serde_json::to_string(v).unwrap().into();
+        self
+    }
+
+    #[doc =
+        "Set the field `name` to already serialized JSON, for instance a large document loaded from a file. `v` is stored as-is without being parsed or checked, so invalid JSON is only reported by Terraform or the provider."]
+    pub fn set_name_raw_json(self, v: &str) -> Self {
+        self.0.data.borrow_mut().name = v.to_string().into();
+        self
+    }
+
+    #[doc = "Set the field `name` to a duration, formatted as seconds (ex: `90s`)."]
+    pub fn set_name_duration(self, v: std::time::Duration) -> Self {
+        self.0.data.borrow_mut().name = format_duration(v).into();
+        self
+    }
+
+    #[doc = "Set the field `name` to a size in bytes, formatted with a binary suffix (ex: `10Gi`)."]
+    pub fn set_name_size(self, v: u64) -> Self {
+        self.0.data.borrow_mut().name = format_size(v).into();
+        self
+    }
+
+    #[doc = "Set the field `num_map`.\n"]
+    pub fn set_num_map(self, v: impl Into<RecField<PrimField<f64>>>) -> Self {
+        self.0.data.borrow_mut().num_map = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `num_map` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_num_map_opt(self, v: Option<RecField<PrimField<f64>>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().num_map = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `obj_map`.\n"]
+    pub fn set_obj_map(self, v: impl Into<RecField<ThingObjMapEl>>) -> Self {
+        self.0.data.borrow_mut().obj_map = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `obj_map` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_obj_map_opt(self, v: Option<RecField<ThingObjMapEl>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().obj_map = Some(v);
+        }
+        self
+    }
+
+    #[doc =
+        "Set the write-only field `password_wo` and `password_wo_version`. Change the version to make Terraform update the value."]
+    pub fn set_password_wo_with_version(
+        self,
+        v: impl Into<PrimField<String>>,
+        version: impl Into<PrimField<f64>>,
+    ) -> Self {
+        self.0.data.borrow_mut().password_wo = Some(v.into());
+        self.0.data.borrow_mut().password_wo_version = Some(version.into());
+        self
+    }
+
+    #[doc =
+        "Set the field `password_wo`.\n\n\n# Sensitive\n\nThe provider marks this as sensitive. Terraform hides it in plan output, but avoid logging it yourself.\n\nThis is write-only: it isn't stored in the state and can't be referenced."]
+    pub fn set_password_wo(self, v: impl Into<PrimField<String>>) -> Self {
+        self.0.data.borrow_mut().password_wo = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `password_wo` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_password_wo_opt(self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().password_wo = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `password_wo_version`.\n"]
+    pub fn set_password_wo_version(self, v: impl Into<PrimField<f64>>) -> Self {
+        self.0.data.borrow_mut().password_wo_version = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `password_wo_version` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_password_wo_version_opt(self, v: Option<PrimField<f64>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().password_wo_version = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `raw`.\nAn attribute with the same name as a generated field"]
+    pub fn set_raw(self, v: impl Into<PrimField<String>>) -> Self {
+        self.0.data.borrow_mut().raw = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `raw` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_raw_opt(self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().raw = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `setted`.\n"]
+    pub fn set_setted(self, v: impl Into<Vec<ThingSettedEl>>) -> Self {
+        self.0.data.borrow_mut().setted = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `setted` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_setted_opt(self, v: Option<Vec<ThingSettedEl>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().setted = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `single`.\n"]
+    pub fn set_single(self, v: impl Into<ThingSingle>) -> Self {
+        self.0.data.borrow_mut().single = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `single` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_single_opt(self, v: Option<ThingSingle>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().single = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `tags_all`.\n"]
+    pub fn set_tags_all(self, v: impl Into<RecField<PrimField<String>>>) -> Self {
+        self.0.data.borrow_mut().tags_all = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `tags_all` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_tags_all_opt(self, v: Option<RecField<PrimField<String>>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().tags_all = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `bgroup`.\n"]
+    pub fn set_bgroup(self, v: impl Into<ThingBgroupEl>) -> Self {
+        self.0.data.borrow_mut().bgroup = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `bgroup` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_bgroup_opt(self, v: Option<ThingBgroupEl>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().bgroup = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `blist`.\n"]
+    pub fn set_blist(self, v: impl Into<BlockAssignable<ThingBlistEl>>) -> Self {
+        match v.into() {
+            BlockAssignable::Literal(v) => {
+                self.0.data.borrow_mut().blist = Some(v);
+            },
+            BlockAssignable::Dynamic(d) => {
+                self.0.data.borrow_mut().dynamic.blist = Some(d);
+            },
+        }
+        self
+    }
+
+    #[doc = "Set the field `bmap`.\n"]
+    pub fn set_bmap(self, v: impl Into<RecField<ThingBmapEl>>) -> Self {
+        self.0.data.borrow_mut().bmap = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `bmap` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_bmap_opt(self, v: Option<RecField<ThingBmapEl>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().bmap = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Get a reference to the value of field `any_list` after provisioning.\n"]
+    pub fn any_list(&self) -> ListRef<AnyRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("any_list"))
+    }
+
+    #[doc = "Get a reference to the value of field `any_out` after provisioning.\n"]
+    pub fn any_out(&self) -> AnyRef {
+        AnyRef::new(self.shared().clone(), self.attr_ref("any_out"))
+    }
+
+    #[doc = "Get a reference to the value of field `anything` after provisioning.\n"]
+    pub fn anything(&self) -> AnyRef {
+        AnyRef::new(self.shared().clone(), self.attr_ref("anything"))
+    }
+
+    #[doc = "Get a reference to the value of field `bool_map` after provisioning.\n"]
+    pub fn bool_map(&self) -> RecRef<PrimExpr<bool>> {
+        RecRef::new(self.shared().clone(), self.attr_ref("bool_map"))
+    }
+
+    #[doc = "Get a reference to the value of field `id` after provisioning.\n"]
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("id"))
+    }
+
+    #[doc = "Get a reference to the value of field `list_map` after provisioning.\n"]
+    pub fn list_map(&self) -> RecRef<ListRef<PrimExpr<String>>> {
+        RecRef::new(self.shared().clone(), self.attr_ref("list_map"))
+    }
+
+    #[doc = "Get a reference to the value of field `listed` after provisioning.\n"]
+    pub fn listed(&self) -> ListRef<ThingListedElRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("listed"))
+    }
+
+    #[doc = "Get a reference to the value of field `mapped` after provisioning.\n"]
+    pub fn mapped(&self) -> RecRef<ThingMappedElRef> {
+        RecRef::new(self.shared().clone(), self.attr_ref("mapped"))
+    }
+
+    #[doc = "Get a reference to the value of field `name` after provisioning.\nThe name"]
+    pub fn name(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("name"))
+    }
+
+    #[doc = "Get a reference to the value of field `num_map` after provisioning.\n"]
+    pub fn num_map(&self) -> RecRef<PrimExpr<f64>> {
+        RecRef::new(self.shared().clone(), self.attr_ref("num_map"))
+    }
+
+    #[doc = "Get a reference to the value of field `obj_map` after provisioning.\n"]
+    pub fn obj_map(&self) -> RecRef<ThingObjMapElRef> {
+        RecRef::new(self.shared().clone(), self.attr_ref("obj_map"))
+    }
+
+    #[doc = "Get a reference to the value of field `password_wo_version` after provisioning.\n"]
+    pub fn password_wo_version(&self) -> PrimExpr<f64> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("password_wo_version"))
+    }
+
+    #[doc =
+        "Get a reference to the value of field `raw` after provisioning.\nAn attribute with the same name as a generated field"]
+    pub fn raw(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("raw"))
+    }
+
+    #[doc = "Get a reference to the value of field `setted` after provisioning.\n"]
+    pub fn setted(&self) -> SetRef<ThingSettedElRef> {
+        SetRef::new(self.shared().clone(), self.attr_ref("setted"))
+    }
+
+    #[doc = "Get a reference to the value of field `single` after provisioning.\n"]
+    pub fn single(&self) -> ThingSingleRef {
+        ThingSingleRef::new(self.shared().clone(), self.attr_ref("single"))
+    }
+
+    #[doc = "Get a reference to the value of field `tags_all` after provisioning.\n"]
+    pub fn tags_all(&self) -> RecRef<PrimExpr<String>> {
+        RecRef::new(self.shared().clone(), self.attr_ref("tags_all"))
+    }
+
+    #[doc = "Get a reference to the value of field `bgroup` after provisioning.\n"]
+    pub fn bgroup(&self) -> ThingBgroupElRef {
+        ThingBgroupElRef::new(self.shared().clone(), self.attr_ref("bgroup"))
+    }
+
+    #[doc = "Get a reference to the value of field `blist` after provisioning.\n"]
+    pub fn blist(&self) -> ListRef<ThingBlistElRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("blist"))
+    }
+
+    #[doc = "Get a reference to the value of field `bmap` after provisioning.\n"]
+    pub fn bmap(&self) -> RecRef<ThingBmapElRef> {
+        RecRef::new(self.shared().clone(), self.attr_ref("bmap"))
+    }
+}
+
+impl Referable for Thing {
+    fn extract_ref(&self) -> String {
+        format!("{}.{}", self.0.extract_resource_type(), self.0.extract_tf_id())
+    }
+}
+
+impl Resource for Thing { }
+
+impl std::fmt::Debug for Thing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(stringify!(Thing)).field("type", &"fake_thing").field("tf_id", &self.0.tf_id).finish()
+    }
+}
+
+impl ToListMappable for Thing {
+    type O = ListRef<ThingRef>;
+
+    fn do_map(self, base: String) -> Self::O {
+        self.0.data.borrow_mut().for_each = Some(format!("${{{}}}", base));
+        ListRef::new(self.0.shared.clone(), self.extract_ref())
+    }
+}
+
+impl Resource_ for Thing_ {
+    fn extract_resource_type(&self) -> String {
+        "fake_thing".into()
+    }
+
+    fn extract_tf_id(&self) -> String {
+        self.tf_id.clone()
+    }
+
+    fn extract_value(&self) -> serde_json::Value {
+        serde_json::to_value(&self.data).unwrap()
+    }
+}
+
+pub struct BuildThing {
+    pub tf_id: String,
+    #[doc = "The name"]
+    pub name: PrimField<String>,
+}
+
+impl BuildThing {
+    fn create(self, stack: &Stack) -> Thing {
+        Thing(Rc::new(Thing_ {
+            shared: stack.shared.clone(),
+            tf_id: self.tf_id,
+            data: RefCell::new(ThingData {
+                depends_on: core::default::Default::default(),
+                provider: None,
+                lifecycle: core::default::Default::default(),
+                for_each: None,
+                count: None,
+                any_list: core::default::Default::default(),
+                anything: core::default::Default::default(),
+                list_map: core::default::Default::default(),
+                listed: core::default::Default::default(),
+                mapped: core::default::Default::default(),
+                name: self.name,
+                num_map: core::default::Default::default(),
+                obj_map: core::default::Default::default(),
+                password_wo: core::default::Default::default(),
+                password_wo_version: core::default::Default::default(),
+                raw: core::default::Default::default(),
+                setted: core::default::Default::default(),
+                single: core::default::Default::default(),
+                tags_all: core::default::Default::default(),
+                bgroup: core::default::Default::default(),
+                blist: core::default::Default::default(),
+                bmap: core::default::Default::default(),
+                dynamic: Default::default(),
+                __terrars_raw: Default::default(),
+            }),
+        }))
+    }
+
+    pub fn build(self, stack: &mut Stack) -> Thing {
+        let out = self.create(stack);
+        stack.add_resource(out.0.clone());
+        out
+    }
+
+    #[doc =
+        "Like `build` but fails instead of adding the resource if `tf_id` isn't a valid Terraform identifier or is already used by another resource of the same type."]
+    pub fn try_build(self, stack: &mut Stack) -> Result<Thing, StackError> {
+        let out = self.create(stack);
+        stack.try_add_resource(out.0.clone())?;
+        Ok(out)
+    }
+}
+
+pub struct ThingRef {
+    shared: StackShared,
+    base: String,
+    guarded: bool,
+}
+
+impl Ref for ThingRef {
+    fn new(shared: StackShared, base: String) -> Self {
+        Self {
+            shared: shared,
+            base: base,
+            guarded: false,
+        }
+    }
+}
+
+impl ThingRef {
+    #[doc =
+        "Create a reference to an existing resource by its address (ex: `type.name`), for instance one managed outside this stack."]
+    pub fn from_address(shared: StackShared, address: &str) -> Self {
+        Self::new(shared, address.to_string())
+    }
+
+    #[doc = "Refer to one instance of a resource created with `count`."]
+    pub fn index(&self, i: usize) -> Self {
+        Self::new(self.shared.clone(), format!("{}[{}]", self.base, i))
+    }
+
+    #[doc = "Refer to one instance of a resource created with `for_each` by its key."]
+    pub fn instance(&self, key: &str) -> Self {
+        Self::new(self.shared.clone(), format!("{}[{}]", self.base, key.to_string().to_expr_raw()))
+    }
+
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    fn attr_ref(&self, attr: &str) -> String {
+        if self.guarded {
+            format!("one({}[*].{})", self.base, attr)
+        } else {
+            format!("{}.{}", self.base, attr)
+        }
+    }
+
+    #[doc = "Get a reference to the value of field `any_list` after provisioning.\n"]
+    pub fn any_list(&self) -> ListRef<AnyRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("any_list"))
+    }
+
+    #[doc = "Get a reference to the value of field `any_out` after provisioning.\n"]
+    pub fn any_out(&self) -> AnyRef {
+        AnyRef::new(self.shared().clone(), self.attr_ref("any_out"))
+    }
+
+    #[doc = "Get a reference to the value of field `anything` after provisioning.\n"]
+    pub fn anything(&self) -> AnyRef {
+        AnyRef::new(self.shared().clone(), self.attr_ref("anything"))
+    }
+
+    #[doc = "Get a reference to the value of field `bool_map` after provisioning.\n"]
+    pub fn bool_map(&self) -> RecRef<PrimExpr<bool>> {
+        RecRef::new(self.shared().clone(), self.attr_ref("bool_map"))
+    }
+
+    #[doc = "Get a reference to the value of field `id` after provisioning.\n"]
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("id"))
+    }
+
+    #[doc = "Get a reference to the value of field `list_map` after provisioning.\n"]
+    pub fn list_map(&self) -> RecRef<ListRef<PrimExpr<String>>> {
+        RecRef::new(self.shared().clone(), self.attr_ref("list_map"))
+    }
+
+    #[doc = "Get a reference to the value of field `listed` after provisioning.\n"]
+    pub fn listed(&self) -> ListRef<ThingListedElRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("listed"))
+    }
+
+    #[doc = "Get a reference to the value of field `mapped` after provisioning.\n"]
+    pub fn mapped(&self) -> RecRef<ThingMappedElRef> {
+        RecRef::new(self.shared().clone(), self.attr_ref("mapped"))
+    }
+
+    #[doc = "Get a reference to the value of field `name` after provisioning.\nThe name"]
+    pub fn name(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("name"))
+    }
+
+    #[doc = "Get a reference to the value of field `num_map` after provisioning.\n"]
+    pub fn num_map(&self) -> RecRef<PrimExpr<f64>> {
+        RecRef::new(self.shared().clone(), self.attr_ref("num_map"))
+    }
+
+    #[doc = "Get a reference to the value of field `obj_map` after provisioning.\n"]
+    pub fn obj_map(&self) -> RecRef<ThingObjMapElRef> {
+        RecRef::new(self.shared().clone(), self.attr_ref("obj_map"))
+    }
+
+    #[doc = "Get a reference to the value of field `password_wo_version` after provisioning.\n"]
+    pub fn password_wo_version(&self) -> PrimExpr<f64> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("password_wo_version"))
+    }
+
+    #[doc =
+        "Get a reference to the value of field `raw` after provisioning.\nAn attribute with the same name as a generated field"]
+    pub fn raw(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("raw"))
+    }
+
+    #[doc = "Get a reference to the value of field `setted` after provisioning.\n"]
+    pub fn setted(&self) -> SetRef<ThingSettedElRef> {
+        SetRef::new(self.shared().clone(), self.attr_ref("setted"))
+    }
+
+    #[doc = "Get a reference to the value of field `single` after provisioning.\n"]
+    pub fn single(&self) -> ThingSingleRef {
+        ThingSingleRef::new(self.shared().clone(), self.attr_ref("single"))
+    }
+
+    #[doc = "Get a reference to the value of field `tags_all` after provisioning.\n"]
+    pub fn tags_all(&self) -> RecRef<PrimExpr<String>> {
+        RecRef::new(self.shared().clone(), self.attr_ref("tags_all"))
+    }
+
+    #[doc = "Get a reference to the value of field `bgroup` after provisioning.\n"]
+    pub fn bgroup(&self) -> ThingBgroupElRef {
+        ThingBgroupElRef::new(self.shared().clone(), self.attr_ref("bgroup"))
+    }
+
+    #[doc = "Get a reference to the value of field `blist` after provisioning.\n"]
+    pub fn blist(&self) -> ListRef<ThingBlistElRef> {
+        ListRef::new(self.shared().clone(), self.attr_ref("blist"))
+    }
+
+    #[doc = "Get a reference to the value of field `bmap` after provisioning.\n"]
+    pub fn bmap(&self) -> RecRef<ThingBmapElRef> {
+        RecRef::new(self.shared().clone(), self.attr_ref("bmap"))
+    }
+}
+
+impl Referable for ThingRef {
+    fn extract_ref(&self) -> String {
+        self.base.clone()
+    }
+}
+
+#[derive(Serialize)]
+pub struct ThingListedEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    a: Option<PrimField<String>>,
+}
+
+impl ThingListedEl {
+    #[doc = "Set the field `a`.\n"]
+    #[must_use]
+    pub fn set_a(mut self, v: impl Into<PrimField<String>>) -> Self {
+        self.a = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `a` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_a_opt(mut self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.a = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for ThingListedEl {
+    type O = BlockAssignable<ThingListedEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildThingListedEl {}
+
+impl BuildThingListedEl {
+    pub fn build(self) -> ThingListedEl {
+        ThingListedEl { a: core::default::Default::default() }
+    }
+}
+
+pub struct ThingListedElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for ThingListedElRef {
+    fn new(shared: StackShared, base: String) -> ThingListedElRef {
+        ThingListedElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl ThingListedElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `a` after provisioning.\n"]
+    pub fn a(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.a", self.base))
+    }
+
+    #[doc = "Get a reference to the value of field `c` after provisioning.\n"]
+    pub fn c(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.c", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct ThingMappedEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    a: Option<PrimField<String>>,
+}
+
+impl ThingMappedEl {
+    #[doc = "Set the field `a`.\n"]
+    #[must_use]
+    pub fn set_a(mut self, v: impl Into<PrimField<String>>) -> Self {
+        self.a = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `a` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_a_opt(mut self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.a = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for ThingMappedEl {
+    type O = BlockAssignable<ThingMappedEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildThingMappedEl {}
+
+impl BuildThingMappedEl {
+    pub fn build(self) -> ThingMappedEl {
+        ThingMappedEl { a: core::default::Default::default() }
+    }
+}
+
+pub struct ThingMappedElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for ThingMappedElRef {
+    fn new(shared: StackShared, base: String) -> ThingMappedElRef {
+        ThingMappedElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl ThingMappedElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `a` after provisioning.\n"]
+    pub fn a(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.a", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct ThingObjMapEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    x: Option<PrimField<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    y: Option<PrimField<f64>>,
+}
+
+impl ThingObjMapEl {
+    #[doc = "Set the field `x`.\n"]
+    #[must_use]
+    pub fn set_x(mut self, v: impl Into<PrimField<String>>) -> Self {
+        self.x = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `x` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_x_opt(mut self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.x = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Set the field `y`.\n"]
+    #[must_use]
+    pub fn set_y(mut self, v: impl Into<PrimField<f64>>) -> Self {
+        self.y = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `y` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_y_opt(mut self, v: Option<PrimField<f64>>) -> Self {
+        if let Some(v) = v {
+            self.y = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for ThingObjMapEl {
+    type O = BlockAssignable<ThingObjMapEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildThingObjMapEl {}
+
+impl BuildThingObjMapEl {
+    pub fn build(self) -> ThingObjMapEl {
+        ThingObjMapEl {
+            x: core::default::Default::default(),
+            y: core::default::Default::default(),
+        }
+    }
+}
+
+pub struct ThingObjMapElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for ThingObjMapElRef {
+    fn new(shared: StackShared, base: String) -> ThingObjMapElRef {
+        ThingObjMapElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl ThingObjMapElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `x` after provisioning.\n"]
+    pub fn x(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.x", self.base))
+    }
+
+    #[doc = "Get a reference to the value of field `y` after provisioning.\n"]
+    pub fn y(&self) -> PrimExpr<f64> {
+        PrimExpr::new(self.shared().clone(), format!("{}.y", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct ThingSettedEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    a: Option<PrimField<String>>,
+}
+
+impl ThingSettedEl {
+    #[doc = "Set the field `a`.\n"]
+    #[must_use]
+    pub fn set_a(mut self, v: impl Into<PrimField<String>>) -> Self {
+        self.a = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `a` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_a_opt(mut self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.a = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for ThingSettedEl {
+    type O = BlockAssignable<ThingSettedEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildThingSettedEl {}
+
+impl BuildThingSettedEl {
+    pub fn build(self) -> ThingSettedEl {
+        ThingSettedEl { a: core::default::Default::default() }
+    }
+}
+
+pub struct ThingSettedElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for ThingSettedElRef {
+    fn new(shared: StackShared, base: String) -> ThingSettedElRef {
+        ThingSettedElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl ThingSettedElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `a` after provisioning.\n"]
+    pub fn a(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.a", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct ThingSingle {
+    a: PrimField<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    b: Option<PrimField<f64>>,
+}
+
+impl ThingSingle {
+    #[doc = "Set the field `b`.\n"]
+    #[must_use]
+    pub fn set_b(mut self, v: impl Into<PrimField<f64>>) -> Self {
+        self.b = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `b` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_b_opt(mut self, v: Option<PrimField<f64>>) -> Self {
+        if let Some(v) = v {
+            self.b = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for ThingSingle {
+    type O = BlockAssignable<ThingSingle>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildThingSingle {
+    pub a: PrimField<String>,
+}
+
+impl BuildThingSingle {
+    pub fn build(self) -> ThingSingle {
+        ThingSingle {
+            a: self.a,
+            b: core::default::Default::default(),
+        }
+    }
+}
+
+pub struct ThingSingleRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for ThingSingleRef {
+    fn new(shared: StackShared, base: String) -> ThingSingleRef {
+        ThingSingleRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl ThingSingleRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `a` after provisioning.\n"]
+    pub fn a(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.a", self.base))
+    }
+
+    #[doc = "Get a reference to the value of field `b` after provisioning.\n"]
+    pub fn b(&self) -> PrimExpr<f64> {
+        PrimExpr::new(self.shared().clone(), format!("{}.b", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct ThingBgroupEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    x: Option<PrimField<String>>,
+}
+
+impl ThingBgroupEl {
+    #[doc = "Set the field `x`.\n"]
+    #[must_use]
+    pub fn set_x(mut self, v: impl Into<PrimField<String>>) -> Self {
+        self.x = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `x` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_x_opt(mut self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.x = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for ThingBgroupEl {
+    type O = BlockAssignable<ThingBgroupEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildThingBgroupEl {}
+
+impl BuildThingBgroupEl {
+    pub fn build(self) -> ThingBgroupEl {
+        ThingBgroupEl { x: core::default::Default::default() }
+    }
+}
+
+pub struct ThingBgroupElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for ThingBgroupElRef {
+    fn new(shared: StackShared, base: String) -> ThingBgroupElRef {
+        ThingBgroupElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl ThingBgroupElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `x` after provisioning.\n"]
+    pub fn x(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.x", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct ThingBlistEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    x: Option<PrimField<String>>,
+}
+
+impl ThingBlistEl {
+    #[doc = "Set the field `x` to a duration, formatted as seconds (ex: `90s`)."]
+    #[must_use]
+    pub fn set_x_duration(mut self, v: std::time::Duration) -> Self {
+        self.x = Some(format_duration(v).into());
+        self
+    }
+
+    #[doc = "Set the field `x`.\n"]
+    #[must_use]
+    pub fn set_x(mut self, v: impl Into<PrimField<String>>) -> Self {
+        self.x = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `x` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_x_opt(mut self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.x = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for ThingBlistEl {
+    type O = BlockAssignable<ThingBlistEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildThingBlistEl {}
+
+impl BuildThingBlistEl {
+    pub fn build(self) -> ThingBlistEl {
+        ThingBlistEl { x: core::default::Default::default() }
+    }
+}
+
+pub struct ThingBlistElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for ThingBlistElRef {
+    fn new(shared: StackShared, base: String) -> ThingBlistElRef {
+        ThingBlistElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl ThingBlistElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `x` after provisioning.\n"]
+    pub fn x(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.x", self.base))
+    }
+}
+
+#[derive(Serialize)]
+pub struct ThingBmapEl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    x: Option<PrimField<String>>,
+}
+
+impl ThingBmapEl {
+    #[doc = "Set the field `x`.\n"]
+    #[must_use]
+    pub fn set_x(mut self, v: impl Into<PrimField<String>>) -> Self {
+        self.x = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `x` if `v` is `Some`, otherwise leave it unchanged."]
+    #[must_use]
+    pub fn set_x_opt(mut self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.x = Some(v);
+        }
+        self
+    }
+}
+
+impl ToListMappable for ThingBmapEl {
+    type O = BlockAssignable<ThingBmapEl>;
+
+    fn do_map(self, base: String) -> Self::O {
+        BlockAssignable::Dynamic(DynamicBlock {
+            for_each: format!("${{{}}}", base),
+            iterator: "each".into(),
+            content: self,
+        })
+    }
+}
+
+pub struct BuildThingBmapEl {}
+
+impl BuildThingBmapEl {
+    pub fn build(self) -> ThingBmapEl {
+        ThingBmapEl { x: core::default::Default::default() }
+    }
+}
+
+pub struct ThingBmapElRef {
+    shared: StackShared,
+    base: String,
+}
+
+impl Ref for ThingBmapElRef {
+    fn new(shared: StackShared, base: String) -> ThingBmapElRef {
+        ThingBmapElRef {
+            shared: shared,
+            base: base.to_string(),
+        }
+    }
+}
+
+impl ThingBmapElRef {
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    #[doc = "Get a reference to the value of field `x` after provisioning.\n"]
+    pub fn x(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), format!("{}.x", self.base))
+    }
+}
+
+#[derive(Serialize, Default)]
+struct ThingDynamic {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blist: Option<DynamicBlock<ThingBlistEl>>,
+}
+
+impl ThingDynamic {
+    fn is_empty(&self) -> bool {
+        self.blist.is_none()
+    }
+}
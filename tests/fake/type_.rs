@@ -0,0 +1,351 @@
+use serde::Serialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+use terrars::*;
+use super::provider::ProviderFake;
+
+#[derive(Serialize)]
+struct TypeData {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<String>,
+    #[serde(skip_serializing_if = "SerdeSkipDefault::is_default")]
+    lifecycle: ResourceLifecycle,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    for_each: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<PrimField<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<PrimField<String>>,
+    #[serde(flatten)]
+    __terrars_raw: std::collections::BTreeMap<String, AnyField>,
+}
+
+struct Type_ {
+    shared: StackShared,
+    tf_id: String,
+    data: RefCell<TypeData>,
+}
+
+#[derive(Clone)]
+pub struct Type(Rc<Type_>);
+
+impl Type {
+    fn shared(&self) -> &StackShared {
+        &self.0.shared
+    }
+
+    fn attr_ref(&self, attr: &str) -> String {
+        format!("{}.{}", self.extract_ref(), attr)
+    }
+
+    pub fn depends_on(self, dep: &impl Referable) -> Self {
+        self.0.data.borrow_mut().depends_on.push(dep.extract_ref());
+        self
+    }
+
+    pub fn set_provider(self, provider: &ProviderFake) -> Self {
+        self.0.data.borrow_mut().provider = Some(provider.provider_ref());
+        self
+    }
+
+    #[doc =
+        "Create `v` instances of the resource (`count`). Use `index` to refer to an instance. Can't be combined with `for_each` (ex: creating the resource with `map`)."]
+    pub fn set_count(self, v: impl Into<PrimField<i64>>) -> Self {
+        self.0.data.borrow_mut().count = Some(v.into());
+        self
+    }
+
+    #[doc =
+        "Only create the resource if `cond` is true (sets `count` to 1 or 0). Use `enabled_ref` to refer to the resource. Can't be combined with `for_each` (ex: creating the resource with `map`)."]
+    pub fn set_enabled_if(self, cond: &impl Expr<bool>) -> Self {
+        let count = self.shared().add_sentinel(&format!("{} ? 1 : 0", cond.expr_raw().1));
+        self.0.data.borrow_mut().count = Some(PrimField::Sentinel(count));
+        self
+    }
+
+    #[doc =
+        "Refer to the resource when it was made conditional with `set_enabled_if`. Attributes are `null` when the resource is disabled. Values nested in blocks aren't guarded, so only refer to them when the condition is true."]
+    pub fn enabled_ref(&self) -> TypeRef {
+        TypeRef {
+            shared: self.shared().clone(),
+            base: self.extract_ref(),
+            guarded: true,
+        }
+    }
+
+    #[doc = "Refer to one instance of a resource created with `set_count`."]
+    pub fn index(&self, i: usize) -> TypeRef {
+        TypeRef::new(self.shared().clone(), format!("{}[{}]", self.extract_ref(), i))
+    }
+
+    #[doc =
+        "Refer to one instance of a resource created with `for_each` (ex: with `map`) by its key. Use `extract_ref` on the result to get the address for `RunOptions::targets` or `RunOptions::replace`."]
+    pub fn instance(&self, key: &str) -> TypeRef {
+        TypeRef::new(self.shared().clone(), format!("{}[{}]", self.extract_ref(), key.to_string().to_expr_raw()))
+    }
+
+    pub fn set_create_before_destroy(self, v: bool) -> Self {
+        self.0.data.borrow_mut().lifecycle.create_before_destroy = v;
+        self
+    }
+
+    pub fn set_prevent_destroy(self, v: bool) -> Self {
+        self.0.data.borrow_mut().lifecycle.prevent_destroy = v;
+        self
+    }
+
+    pub fn ignore_changes_to_all(self) -> Self {
+        self.0.data.borrow_mut().lifecycle.ignore_changes = Some(IgnoreChanges::All(IgnoreChangesAll::All));
+        self
+    }
+
+    pub fn ignore_changes_to_attr(self, attr: impl ToString) -> Self {
+        {
+            let mut d = self.0.data.borrow_mut();
+            if match &mut d.lifecycle.ignore_changes {
+                Some(i) => match i {
+                    IgnoreChanges::All(_) => {
+                        true
+                    },
+                    IgnoreChanges::Refs(r) => {
+                        r.push(attr.to_string());
+                        false
+                    },
+                },
+                None => true,
+            } {
+                d.lifecycle.ignore_changes = Some(IgnoreChanges::Refs(vec![attr.to_string()]));
+            }
+        }
+        self
+    }
+
+    pub fn replace_triggered_by_resource(self, r: &impl Resource) -> Self {
+        self.0.data.borrow_mut().lifecycle.replace_triggered_by.push(r.extract_ref());
+        self
+    }
+
+    pub fn replace_triggered_by_attr(self, attr: impl ToString) -> Self {
+        self.0.data.borrow_mut().lifecycle.replace_triggered_by.push(attr.to_string());
+        self
+    }
+
+    #[doc = "Remove all attributes set with `ignore_changes_to_attr` or `ignore_changes_to_all`."]
+    pub fn clear_ignore_changes(self) -> Self {
+        self.0.data.borrow_mut().lifecycle.ignore_changes = None;
+        self
+    }
+
+    #[doc = "Remove all references added with `replace_triggered_by_resource` or `replace_triggered_by_attr`."]
+    pub fn clear_replace_triggered_by(self) -> Self {
+        self.0.data.borrow_mut().lifecycle.replace_triggered_by.clear();
+        self
+    }
+
+    #[doc = "Reset all `lifecycle` settings to their defaults."]
+    pub fn clear_lifecycle(self) -> Self {
+        self.0.data.borrow_mut().lifecycle = Default::default();
+        self
+    }
+
+    #[doc =
+        "Merge a value that serializes to a json object into the `lifecycle` block (ex: arguments added in newer Terraform versions). This bypasses the typed lifecycle setters and takes precedence over them. Panics if the value doesn't serialize to an object."]
+    pub fn set_lifecycle_raw(self, v: impl Serialize) -> Self {
+        match serde_json::to_value(v).unwrap() {
+            serde_json::Value::Object(o) => self.0.data.borrow_mut().lifecycle.raw.extend(o),
+            _ => panic!("set_lifecycle_raw value must serialize to a json object"),
+        }
+        self
+    }
+
+    #[doc =
+        "Set attributes and blocks from a value that serializes to a json object in Terraform's json syntax (ex: the resource body from an existing `.tf.json` file). This bypasses type checking and takes precedence over values set with the typed setters. Strings are escaped like other literal strings, but may contain expressions converted to strings (ex: with `to_string`). Panics if the value doesn't serialize to an object."]
+    pub fn set_from(self, v: impl Serialize) -> Self {
+        match serde_json::to_value(v).unwrap() {
+            serde_json::Value::Object(o) => self
+                .0
+                .data
+                .borrow_mut()
+                .__terrars_raw
+                .extend(o.into_iter().map(|(k, v)| (k, AnyField::Literal(v)))),
+            _ => panic!("set_from value must serialize to a json object"),
+        }
+        self
+    }
+
+    #[doc =
+        "Set a single attribute or block in Terraform's json syntax, for last-minute overrides (ex: with a `serde_json::Value` or an expression). Like `set_from`, this bypasses type checking and takes precedence over values set with the typed setters."]
+    pub fn set_data_raw(&self, k: impl ToString, v: impl Into<AnyField>) {
+        self.0.data.borrow_mut().__terrars_raw.insert(k.to_string(), v.into());
+    }
+
+    #[doc =
+        "Get the current value of an attribute or block as it will be serialized, or `None` if it isn't set. Expressions appear as `_TERRARS_SENTINEL*` placeholders."]
+    pub fn get_data_raw(&self, k: &str) -> Option<serde_json::Value> {
+        match serde_json::to_value(&self.0.data).unwrap() {
+            serde_json::Value::Object(mut o) => o.remove(k),
+            _ => None,
+        }
+    }
+
+    #[doc = "Set the field `name`.\n"]
+    pub fn set_name(self, v: impl Into<PrimField<String>>) -> Self {
+        self.0.data.borrow_mut().name = Some(v.into());
+        self
+    }
+
+    #[doc = "Set the field `name` if `v` is `Some`, otherwise leave it unchanged."]
+    pub fn set_name_opt(self, v: Option<PrimField<String>>) -> Self {
+        if let Some(v) = v {
+            self.0.data.borrow_mut().name = Some(v);
+        }
+        self
+    }
+
+    #[doc = "Get a reference to the value of field `id` after provisioning.\n"]
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("id"))
+    }
+
+    #[doc = "Get a reference to the value of field `name` after provisioning.\n"]
+    pub fn name(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("name"))
+    }
+}
+
+impl Referable for Type {
+    fn extract_ref(&self) -> String {
+        format!("{}.{}", self.0.extract_resource_type(), self.0.extract_tf_id())
+    }
+}
+
+impl Resource for Type { }
+
+impl std::fmt::Debug for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(stringify!(Type)).field("type", &"fake_type").field("tf_id", &self.0.tf_id).finish()
+    }
+}
+
+impl ToListMappable for Type {
+    type O = ListRef<TypeRef>;
+
+    fn do_map(self, base: String) -> Self::O {
+        self.0.data.borrow_mut().for_each = Some(format!("${{{}}}", base));
+        ListRef::new(self.0.shared.clone(), self.extract_ref())
+    }
+}
+
+impl Resource_ for Type_ {
+    fn extract_resource_type(&self) -> String {
+        "fake_type".into()
+    }
+
+    fn extract_tf_id(&self) -> String {
+        self.tf_id.clone()
+    }
+
+    fn extract_value(&self) -> serde_json::Value {
+        serde_json::to_value(&self.data).unwrap()
+    }
+}
+
+pub struct BuildType {
+    pub tf_id: String,
+}
+
+impl BuildType {
+    fn create(self, stack: &Stack) -> Type {
+        Type(Rc::new(Type_ {
+            shared: stack.shared.clone(),
+            tf_id: self.tf_id,
+            data: RefCell::new(TypeData {
+                depends_on: core::default::Default::default(),
+                provider: None,
+                lifecycle: core::default::Default::default(),
+                for_each: None,
+                count: None,
+                name: core::default::Default::default(),
+                __terrars_raw: Default::default(),
+            }),
+        }))
+    }
+
+    pub fn build(self, stack: &mut Stack) -> Type {
+        let out = self.create(stack);
+        stack.add_resource(out.0.clone());
+        out
+    }
+
+    #[doc =
+        "Like `build` but fails instead of adding the resource if `tf_id` isn't a valid Terraform identifier or is already used by another resource of the same type."]
+    pub fn try_build(self, stack: &mut Stack) -> Result<Type, StackError> {
+        let out = self.create(stack);
+        stack.try_add_resource(out.0.clone())?;
+        Ok(out)
+    }
+}
+
+pub struct TypeRef {
+    shared: StackShared,
+    base: String,
+    guarded: bool,
+}
+
+impl Ref for TypeRef {
+    fn new(shared: StackShared, base: String) -> Self {
+        Self {
+            shared: shared,
+            base: base,
+            guarded: false,
+        }
+    }
+}
+
+impl TypeRef {
+    #[doc =
+        "Create a reference to an existing resource by its address (ex: `type.name`), for instance one managed outside this stack."]
+    pub fn from_address(shared: StackShared, address: &str) -> Self {
+        Self::new(shared, address.to_string())
+    }
+
+    #[doc = "Refer to one instance of a resource created with `count`."]
+    pub fn index(&self, i: usize) -> Self {
+        Self::new(self.shared.clone(), format!("{}[{}]", self.base, i))
+    }
+
+    #[doc = "Refer to one instance of a resource created with `for_each` by its key."]
+    pub fn instance(&self, key: &str) -> Self {
+        Self::new(self.shared.clone(), format!("{}[{}]", self.base, key.to_string().to_expr_raw()))
+    }
+
+    fn shared(&self) -> &StackShared {
+        &self.shared
+    }
+
+    fn attr_ref(&self, attr: &str) -> String {
+        if self.guarded {
+            format!("one({}[*].{})", self.base, attr)
+        } else {
+            format!("{}.{}", self.base, attr)
+        }
+    }
+
+    #[doc = "Get a reference to the value of field `id` after provisioning.\n"]
+    pub fn id(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("id"))
+    }
+
+    #[doc = "Get a reference to the value of field `name` after provisioning.\n"]
+    pub fn name(&self) -> PrimExpr<String> {
+        PrimExpr::new(self.shared().clone(), self.attr_ref("name"))
+    }
+}
+
+impl Referable for TypeRef {
+    fn extract_ref(&self) -> String {
+        self.base.clone()
+    }
+}
@@ -0,0 +1,489 @@
+//! Tests against bindings generated from `fixtures/schema.json` by
+//! `terrars-generate`, checked in under `fake/`. Regenerate them with
+//!
+//! ```text
+//! cargo run --bin terrars-generate -- tests/fixtures/fake.json --schema tests/fixtures/schema.json
+//! ```
+#[allow(dead_code, unused_imports)]
+mod fake;
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    process::Command,
+};
+use serde_json::{
+    json,
+    Value,
+};
+use terrars::*;
+use fake::*;
+
+fn serialize(stack: &Stack) -> Value {
+    return serde_json::from_slice(&stack.serialize(Path::new("state.tfstate")).unwrap()).unwrap();
+}
+
+fn build_thing(stack: &mut Stack, tf_id: &str) -> Thing {
+    return BuildThing {
+        tf_id: tf_id.into(),
+        name: "n".into(),
+    }.build(stack);
+}
+
+/// Run the generator with `config`, with `dest` set to `dest` in a new temporary
+/// directory.
+fn generate(mut config: Value) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    config["dest"] = json!(dir.path().join("dest"));
+    let config_path = dir.path().join("config.json");
+    fs::write(&config_path, serde_json::to_vec(&config).unwrap()).unwrap();
+    let out =
+        Command::new(env!("CARGO_BIN_EXE_terrars-generate"))
+            .arg(&config_path)
+            .args(["--schema", "tests/fixtures/schema.json"])
+            .output()
+            .unwrap();
+    assert!(out.status.success(), "{}\n{}", String::from_utf8_lossy(&out.stdout), String::from_utf8_lossy(&out.stderr));
+    return dir;
+}
+
+/// The files generated in `dir` by `generate`.
+fn generated_files(dir: &tempfile::TempDir) -> Vec<String> {
+    let mut out =
+        fs::read_dir(dir.path().join("dest"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+    out.sort();
+    return out;
+}
+
+#[test]
+fn generated_up_to_date() {
+    let out =
+        Command::new(env!("CARGO_BIN_EXE_terrars-generate"))
+            .args(["tests/fixtures/fake.json", "--schema", "tests/fixtures/schema.json", "--check"])
+            .output()
+            .unwrap();
+    assert!(out.status.success(), "{}\n{}", String::from_utf8_lossy(&out.stdout), String::from_utf8_lossy(&out.stderr));
+}
+
+#[test]
+fn nested_attributes() {
+    let stack = &mut BuildStack {}.build();
+    let t =
+        build_thing(stack, "t")
+            .set_single(BuildThingSingle { a: "a".into() }.build().set_b(1f64))
+            .set_listed(vec![BuildThingListedEl {}.build().set_a("l")])
+            .set_setted(vec![BuildThingSettedEl {}.build().set_a("s")])
+            .set_mapped(
+                [("k".to_string(), BuildThingMappedEl {}.build().set_a("m"))].into_iter().collect::<HashMap<_, _>>(),
+            );
+    let out = &serialize(stack)["resource"]["fake_thing"]["t"];
+    assert_eq!(out["single"], json!({
+        "a": "a",
+        "b": 1.0
+    }));
+    assert_eq!(out["listed"], json!([{
+        "a": "l"
+    }]));
+    assert_eq!(out["setted"], json!([{
+        "a": "s"
+    }]));
+    assert_eq!(out["mapped"], json!({
+        "k": {
+            "a": "m"
+        }
+    }));
+    assert_eq!(t.single().b().raw(), "fake_thing.t.single.b");
+    assert_eq!(t.listed().get(0).c().raw(), "fake_thing.t.listed[0].c");
+    assert_eq!(t.mapped().get("k").a().raw(), "fake_thing.t.mapped[\"k\"].a");
+}
+
+#[test]
+fn map_and_group_blocks() {
+    let stack = &mut BuildStack {}.build();
+    BuildProviderFake {}
+        .build(stack)
+        .set_endpoints(
+            [("s3".to_string(), BuildFakeEndpointsEl {}.build().set_url("http://s3"))]
+                .into_iter()
+                .collect::<HashMap<_, _>>(),
+        );
+    let t =
+        build_thing(stack, "t")
+            .set_bgroup(BuildThingBgroupEl {}.build().set_x("g"))
+            .set_bmap([("k".to_string(), BuildThingBmapEl {}.build().set_x("m"))].into_iter().collect::<HashMap<_, _>>());
+    let out = serialize(stack);
+    assert_eq!(out["provider"]["fake"], json!([{
+        "endpoints": {
+            "s3": {
+                "url": "http://s3"
+            }
+        }
+    }]));
+    assert_eq!(out["resource"]["fake_thing"]["t"]["bgroup"], json!({
+        "x": "g"
+    }));
+    assert_eq!(out["resource"]["fake_thing"]["t"]["bmap"], json!({
+        "k": {
+            "x": "m"
+        }
+    }));
+    assert_eq!(t.bgroup().x().raw(), "fake_thing.t.bgroup.x");
+    assert_eq!(t.bmap().get("k").x().raw(), "fake_thing.t.bmap[\"k\"].x");
+}
+
+#[test]
+fn nested_computed_refs() {
+    let stack = &mut BuildStack {}.build();
+    let d = BuildDeep { tf_id: "d".into() }.build(stack);
+    assert_eq!(d.deep().get(0).inner().get(0).value().raw(), "fake_deep.d.deep[0].inner[0].value");
+    assert_eq!(d.deep().get(0).m().get("k").q().raw(), "fake_deep.d.deep[0].m[\"k\"].q");
+    assert_eq!(d.nt().get(0).inner().get(0).value().raw(), "fake_deep.d.nt[0].inner[0].value");
+    assert_eq!(d.cblock().get(0).sub().get(0).value().raw(), "fake_deep.d.cblock[0].sub[0].value");
+    let sblock = d.sblock().as_list().get(0).sub().as_list().get(0).value();
+    assert_eq!(sblock.raw(), "tolist(tolist(fake_deep.d.sblock)[0].sub)[0].value");
+    let t = BuildThing {
+        tf_id: "t".into(),
+        name: sblock.into(),
+    }.build(stack);
+    let out = serialize(stack);
+    assert_eq!(out["resource"]["fake_thing"]["t"]["name"], json!("${tolist(tolist(fake_deep.d.sblock)[0].sub)[0].value}"));
+    assert_eq!(t.name().raw(), "fake_thing.t.name");
+}
+
+#[test]
+fn opt_setters() {
+    let stack = &mut BuildStack {}.build();
+    BuildMod { tf_id: "none".into() }.build(stack).set_name_opt(None);
+    BuildMod { tf_id: "some".into() }.build(stack).set_name_opt(Some("x".into()));
+    BuildMod { tf_id: "kept".into() }.build(stack).set_name("x").set_name_opt(None);
+    build_thing(stack, "t").set_anything_opt(None).set_tags_all_opt(None).set_single_opt(None);
+    let out = serialize(stack);
+    assert_eq!(out["resource"]["fake_mod"]["none"].get("name"), None);
+    assert_eq!(out["resource"]["fake_mod"]["some"]["name"], json!("x"));
+    assert_eq!(out["resource"]["fake_mod"]["kept"]["name"], json!("x"));
+    assert_eq!(out["resource"]["fake_thing"]["t"], json!({
+        "name": "n"
+    }));
+}
+
+#[test]
+fn must_use_setters() {
+    let source = fs::read_to_string("tests/fake/thing.rs").unwrap();
+    // Element values are returned by their setters
+    assert!(source.contains("    #[must_use]\n    pub fn set_x(mut self, v: impl Into<PrimField<String>>) -> Self {"));
+    assert!(source.contains("    #[must_use]\n    pub fn set_x_opt(mut self, v: Option<PrimField<String>>) -> Self {"));
+    assert!(source.contains("    #[must_use]\n    pub fn set_x_duration(mut self, v: std::time::Duration) -> Self {"));
+
+    // Resources are shared handles, so the setting isn't lost
+    assert!(source.contains("\n    pub fn set_anything(self, v: impl Into<AnyField>) -> Self {"));
+    assert!(!source.contains("#[must_use]\n    pub fn set_anything("));
+    assert!(!source.contains("#[must_use]\n    pub fn set_name_json("));
+}
+
+#[test]
+fn set_from_escapes_strings() {
+    let stack = &mut BuildStack {}.build();
+    let src = build_thing(stack, "src");
+    build_thing(stack, "t").set_raw("typed").set_from(json!({
+        "extra": "a ${b} %{c}",
+        "nested": {
+            "x": [src.id().to_string(), format!("id-{}", src.id())]
+        },
+        "n": 1
+    }));
+    let out = &serialize(stack)["resource"]["fake_thing"]["t"];
+    assert_eq!(out["raw"], json!("typed"));
+    assert_eq!(out["extra"], json!("a $${b} %%{c}"));
+    assert_eq!(out["nested"], json!({
+        "x": ["${fake_thing.src.id}", "id-${fake_thing.src.id}"]
+    }));
+    assert_eq!(out["n"], json!(1));
+}
+
+#[test]
+fn patch_data_raw() {
+    let stack = &mut BuildStack {}.build();
+    let src = build_thing(stack, "src");
+    let t = build_thing(stack, "t").set_raw("typed");
+    assert_eq!(t.get_data_raw("raw"), Some(json!("typed")));
+    assert_eq!(t.get_data_raw("extra"), None);
+    t.set_data_raw("raw", json!("patched ${x}"));
+    t.set_data_raw("extra", src.id());
+    t.set_data_raw("obj", json!({
+        "a": [1, null]
+    }));
+    assert_eq!(t.get_data_raw("raw"), Some(json!("patched ${x}")));
+    let out = &serialize(stack)["resource"]["fake_thing"]["t"];
+    assert_eq!(out["raw"], json!("patched $${x}"));
+    assert_eq!(out["extra"], json!("${fake_thing.src.id}"));
+    assert_eq!(out["obj"], json!({
+        "a": [1, null]
+    }));
+    assert_eq!(out["name"], json!("n"));
+}
+
+#[test]
+fn block_assignable() {
+    let stack = &mut BuildStack {}.build();
+    let src = build_thing(stack, "src");
+    build_thing(stack, "single").set_blist(BuildThingBlistEl {}.build().set_x("a"));
+    build_thing(stack, "vec").set_blist(vec![BuildThingBlistEl {}.build().set_x("a"), BuildThingBlistEl {}.build().set_x("b")]);
+    build_thing(
+        stack,
+        "dynamic",
+    ).set_blist(DynamicBlock::over(&src.tags_all(), |kv| BuildThingBlistEl {}.build().set_x(kv.value())));
+    let out = &serialize(stack)["resource"]["fake_thing"];
+    assert_eq!(out["single"]["blist"], json!([{
+        "x": "a"
+    }]));
+    assert_eq!(out["vec"]["blist"], json!([{
+        "x": "a"
+    }, {
+        "x": "b"
+    }]));
+    assert_eq!(out["dynamic"].get("blist"), None);
+    assert_eq!(out["dynamic"]["dynamic"]["blist"], json!({
+        "for_each": "${fake_thing.src.tags_all}",
+        "iterator": "each",
+        "content": {
+            "x": "${each.value}"
+        }
+    }));
+}
+
+#[test]
+fn dynamic_attributes() {
+    let stack = &mut BuildStack {}.build();
+    let src = build_thing(stack, "src");
+    build_thing(stack, "t")
+        .set_anything(json!({
+            "a": [1, "${x}"]
+        }))
+        .set_any_list(vec![AnyField::from(json!(true)), AnyField::from(src.any_out().attr("a"))]);
+    build_thing(stack, "expr").set_anything(src.any_out().key("k"));
+    let out = &serialize(stack)["resource"]["fake_thing"];
+    assert_eq!(out["t"]["anything"], json!({
+        "a": [1, "$${x}"]
+    }));
+    assert_eq!(out["t"]["any_list"], json!([true, "${fake_thing.src.any_out.a}"]));
+    assert_eq!(out["expr"]["anything"], json!("${fake_thing.src.any_out[\"k\"]}"));
+    assert_eq!(src.any_out().index(0).as_expr::<String>().raw(), "fake_thing.src.any_out[0]");
+    assert_eq!(src.anything().as_rec::<PrimExpr<f64>>().get("k").raw(), "fake_thing.src.anything[\"k\"]");
+}
+
+#[test]
+fn write_only_attributes() {
+    let stack = &mut BuildStack {}.build();
+    let t = build_thing(stack, "t").set_password_wo_with_version("secret", 2f64);
+    let out = &serialize(stack)["resource"]["fake_thing"]["t"];
+    assert_eq!(out["password_wo"], json!("secret"));
+    assert_eq!(out["password_wo_version"], json!(2.0));
+    assert_eq!(t.password_wo_version().raw(), "fake_thing.t.password_wo_version");
+
+    // Write-only values aren't stored in the state, so there are no refs to them
+    let generated = include_str!("fake/thing.rs");
+    assert!(!generated.contains("pub fn password_wo(&self)"));
+    assert!(generated.contains("pub fn password_wo_version(&self)"));
+}
+
+#[test]
+fn for_each_region() {
+    let stack = &mut BuildStack {}.build();
+    let providers =
+        stack.for_each_region(
+            ["us-east-1", "eu-west-1"],
+            |stack, region| BuildProviderFake {}.build(stack).set_alias(region).set_region(region),
+        );
+    assert_eq!(providers.len(), 2);
+    assert_eq!(providers["us-east-1"].provider_ref(), "fake.us-east-1");
+    assert_eq!(providers["eu-west-1"].provider_ref(), "fake.eu-west-1");
+    assert_eq!(serialize(stack)["provider"]["fake"], json!([{
+        "alias": "us-east-1",
+        "region": "us-east-1"
+    }, {
+        "alias": "eu-west-1",
+        "region": "eu-west-1"
+    }]));
+}
+
+#[test]
+fn null_and_bool_expressions() {
+    let stack = &mut BuildStack {}.build();
+    let single = BuildThingSingle { a: stack.null().into() }.build();
+    let list_map =
+        HashMap::from([("l".to_string(), ListField::from(vec![PrimField::from("a"), stack.null().into()]))]);
+    let tags_all: HashMap<String, PrimField<String>> = HashMap::from([("k".to_string(), stack.null().into())]);
+    let any_list = vec![AnyField::from(stack.null::<String>()), stack.true_().into(), stack.false_().into()];
+    let name = stack.null().into();
+    BuildThing {
+        tf_id: "t".into(),
+        name: name,
+    }
+        .build(stack)
+        .set_single(single)
+        .set_list_map(list_map)
+        .set_tags_all(tags_all)
+        .set_any_list(any_list);
+    let out = &serialize(stack)["resource"]["fake_thing"]["t"];
+    assert_eq!(out["name"], Value::Null);
+    assert_eq!(out["single"], json!({
+        "a": null
+    }));
+    assert_eq!(out["list_map"], json!({
+        "l": ["a", null]
+    }));
+    assert_eq!(out["tags_all"], json!({
+        "k": null
+    }));
+    assert_eq!(out["any_list"], json!([null, "${true}", "${false}"]));
+}
+
+#[test]
+fn count_and_index() {
+    let stack = &mut BuildStack {}.build();
+    let t = build_thing(stack, "t").set_count(2);
+    let d = BuildDataThing {
+        tf_id: "d".into(),
+        id: "x".into(),
+    }.build(stack).set_count(3);
+    let e = BuildEphemeralThing {
+        tf_id: "e".into(),
+        id: "x".into(),
+    }.build(stack).set_count(4);
+    let out = serialize(stack);
+    assert_eq!(out["resource"]["fake_thing"]["t"]["count"], json!(2));
+    assert_eq!(out["data"]["fake_thing"]["d"]["count"], json!(3));
+    assert_eq!(out["ephemeral"]["fake_thing"]["e"]["count"], json!(4));
+    assert_eq!(t.index(1).id().raw(), "fake_thing.t[1].id");
+    assert_eq!(t.instance("k").id().raw(), "fake_thing.t[\"k\"].id");
+    assert_eq!(d.index(0).out().raw(), "data.fake_thing.d[0].out");
+    assert_eq!(d.instance("k").out().raw(), "data.fake_thing.d[\"k\"].out");
+    assert_eq!(e.index(0).out().raw(), "ephemeral.fake_thing.e[0].out");
+    assert_eq!(e.instance("k").out().raw(), "ephemeral.fake_thing.e[\"k\"].out");
+}
+
+#[test]
+fn enabled_ref() {
+    let stack = &mut BuildStack {}.build();
+    let enabled = stack.expr::<bool>("var.enabled");
+    let t = build_thing(stack, "t").set_enabled_if(&enabled);
+    let d = BuildDataThing {
+        tf_id: "d".into(),
+        id: "x".into(),
+    }.build(stack).set_enabled_if(&enabled);
+    let out = serialize(stack);
+    assert_eq!(out["resource"]["fake_thing"]["t"]["count"], json!("${var.enabled ? 1 : 0}"));
+    assert_eq!(t.enabled_ref().id().raw(), "one(fake_thing.t[*].id)");
+    assert_eq!(t.enabled_ref().extract_ref(), "fake_thing.t");
+    assert_eq!(t.enabled_ref().index(0).id().raw(), "fake_thing.t[0].id");
+    assert_eq!(d.enabled_ref().out().raw(), "one(data.fake_thing.d[*].out)");
+    assert_eq!(t.id().raw(), "fake_thing.t.id");
+}
+
+#[test]
+fn json_setters() {
+    let stack = &mut BuildStack {}.build();
+    build_thing(stack, "json").set_name_json(&json!({
+        "a": "${x}"
+    }));
+    build_thing(stack, "raw").set_name_raw_json("{\"a\": \"${x}\"}");
+    build_thing(stack, "invalid").set_name_raw_json("{not json");
+    let out = &serialize(stack)["resource"]["fake_thing"];
+    assert_eq!(out["json"]["name"], json!("{\"a\":\"$${x}\"}"));
+    assert_eq!(out["raw"]["name"], json!("{\"a\": \"$${x}\"}"));
+    assert_eq!(out["invalid"]["name"], json!("{not json"));
+}
+
+#[test]
+fn provider_only() {
+    let dir = generate(json!({
+        "provider": "hashicorp/fake",
+        "version": "1.0.0",
+        "provider_only": true
+    }));
+    assert_eq!(generated_files(&dir), vec![".terrars-manifest", "mod.rs", "provider.rs"]);
+}
+
+#[test]
+fn combined_features() {
+    let cargo_dir = tempfile::tempdir().unwrap();
+    let cargo_path = cargo_dir.path().join("Cargo.toml");
+    fs::write(&cargo_path, "[package]\nname = \"x\"\nversion = \"0.1.0\"\n\n[features]\nmine = []\n").unwrap();
+    generate(json!({
+        "provider": "hashicorp/fake",
+        "version": "1.0.0",
+        "feature_gate": cargo_path
+    }));
+    let manifest: toml::Value = toml::from_str(&fs::read_to_string(&cargo_path).unwrap()).unwrap();
+    let generated = &manifest["features"];
+    assert_eq!(generated["thing"], toml::Value::Array(vec![]));
+    assert_eq!(generated["data_thing"], toml::Value::Array(vec![]));
+    assert_eq!(generated["thing_and_data"], toml::Value::Array(vec!["thing".into(), "data_thing".into()]));
+    assert_eq!(generated.get("deep_and_data"), None);
+    assert_eq!(generated["deep"], toml::Value::Array(vec![]));
+    assert_eq!(generated["mine"], toml::Value::Array(vec![]));
+}
+
+#[test]
+fn emit_required_providers() {
+    let stack = &mut BuildStack {}.build();
+    BuildProviderFake {}.build(stack);
+    assert_eq!(serialize(stack)["terraform"]["required_providers"], json!({
+        "fake": {
+            "source": "hashicorp/fake",
+            "version": "1.0.0"
+        }
+    }));
+    stack.set_emit_required_providers(false);
+    let out = serialize(stack);
+    assert_eq!(out["terraform"].get("required_providers"), None);
+    assert_eq!(out["provider"]["fake"], json!([{}]));
+}
+
+#[test]
+fn clear_lifecycle() {
+    let stack = &mut BuildStack {}.build();
+    let t = build_thing(stack, "t").ignore_changes_to_attr("name").set_prevent_destroy(true).replace_triggered_by_attr("id");
+    assert_eq!(serialize(stack)["resource"]["fake_thing"]["t"]["lifecycle"], json!({
+        "create_before_destroy": false,
+        "prevent_destroy": true,
+        "ignore_changes": ["name"],
+        "replace_triggered_by": ["id"]
+    }));
+    let t = t.clear_ignore_changes();
+    assert_eq!(serialize(stack)["resource"]["fake_thing"]["t"]["lifecycle"], json!({
+        "create_before_destroy": false,
+        "prevent_destroy": true,
+        "replace_triggered_by": ["id"]
+    }));
+    let t = t.clear_replace_triggered_by();
+    assert_eq!(serialize(stack)["resource"]["fake_thing"]["t"]["lifecycle"], json!({
+        "create_before_destroy": false,
+        "prevent_destroy": true
+    }));
+    t.ignore_changes_to_all().clear_lifecycle();
+    assert_eq!(serialize(stack)["resource"]["fake_thing"]["t"].get("lifecycle"), None);
+}
+
+#[test]
+fn skip_empty_collections() {
+    let stack = &mut BuildStack {}.build();
+    build_thing(stack, "t").set_prevent_destroy(true);
+    build_thing(stack, "empty").set_tags_all(HashMap::<String, PrimField<String>>::new());
+    let out = &serialize(stack)["resource"]["fake_thing"];
+    assert_eq!(out["t"], json!({
+        "name": "n",
+        "lifecycle": {
+            "create_before_destroy": false,
+            "prevent_destroy": true
+        }
+    }));
+    assert_eq!(out["empty"], json!({
+        "name": "n",
+        "tags_all": {}
+    }));
+}
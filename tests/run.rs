@@ -0,0 +1,113 @@
+//! Tests that run Terraform, using the built-in `terraform_data` resource so no
+//! providers need to be downloaded. They're skipped if Terraform isn't installed.
+use std::{
+    fs,
+    path::Path,
+    process::Command,
+    rc::Rc,
+};
+use serde_json::{
+    json,
+    Value,
+};
+use terrars::*;
+
+struct Data {
+    tf_id: String,
+    input: String,
+}
+
+impl Resource_ for Data {
+    fn extract_resource_type(&self) -> String {
+        return "terraform_data".to_string();
+    }
+
+    fn extract_tf_id(&self) -> String {
+        return self.tf_id.clone();
+    }
+
+    fn extract_value(&self) -> Value {
+        return json!({
+            "input": self.input
+        });
+    }
+}
+
+fn add_data(stack: &mut Stack, tf_id: &str, input: &str) {
+    stack.add_resource(Rc::new(Data {
+        tf_id: tf_id.to_string(),
+        input: input.to_string(),
+    }));
+}
+
+fn terraform_available() -> bool {
+    if Command::new(get_terraform_binary()).arg("version").output().is_err() {
+        eprintln!("Terraform isn't installed, skipping");
+        return false;
+    }
+    std::env::set_var("TF_CLI_ARGS_apply", "-auto-approve");
+    return true;
+}
+
+/// The addresses of the resources in a state file.
+fn state_addresses(state_path: &Path) -> Vec<String> {
+    let state: Value = serde_json::from_slice(&fs::read(state_path).unwrap()).unwrap();
+    return state["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| format!("{}.{}", r["type"].as_str().unwrap(), r["name"].as_str().unwrap()))
+        .collect();
+}
+
+#[test]
+fn state_mv_rm() {
+    if !terraform_available() {
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    let stack = &mut BuildStack {}.build();
+    add_data(stack, "a", "x");
+    let res = stack.run_with_options(dir.path(), None::<&()>, "apply", &RunOptions::default()).unwrap();
+    assert_eq!(state_addresses(&res.state_path), vec!["terraform_data.a"]);
+    stack.state_mv(dir.path(), "terraform_data.a", "terraform_data.b").unwrap();
+    assert_eq!(state_addresses(&res.state_path), vec!["terraform_data.b"]);
+    stack.state_rm(dir.path(), "terraform_data.b").unwrap();
+    assert!(state_addresses(&res.state_path).is_empty());
+}
+
+/// The `input` of `terraform_data.a` in a state file.
+fn state_input(state_path: &Path) -> Value {
+    let state: Value = serde_json::from_slice(&fs::read(state_path).unwrap()).unwrap();
+    return state["resources"][0]["instances"][0]["attributes"]["input"]["value"].clone();
+}
+
+#[test]
+fn workspaces() {
+    if !terraform_available() {
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    let apply = |workspace: &str, input: &str| -> RunResult {
+        let stack = &mut BuildStack {}.build();
+        add_data(stack, "a", input);
+        return stack.run_with_options(dir.path(), None::<&()>, "apply", &RunOptions {
+            workspace: Some(workspace.to_string()),
+            ..Default::default()
+        }).unwrap();
+    };
+    let one = apply("one", "x");
+    let two = apply("two", "y");
+    assert_eq!(one.state_path, dir.path().join("terraform.tfstate.d/one/terraform.tfstate"));
+    assert_eq!(two.state_path, dir.path().join("terraform.tfstate.d/two/terraform.tfstate"));
+    assert_eq!(state_input(&one.state_path), json!("x"));
+    assert_eq!(state_input(&two.state_path), json!("y"));
+
+    // Nothing was applied in the default workspace
+    assert!(!dir.path().join("state.tfstate").exists());
+
+    // Switching back only changes the selected workspace's state
+    apply("one", "z");
+    assert_eq!(state_input(&one.state_path), json!("z"));
+    assert_eq!(state_input(&two.state_path), json!("y"));
+}